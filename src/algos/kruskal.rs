@@ -0,0 +1,106 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
+
+
+/*
+ * Generates the maze on a grid of any topology by opening the walls between its cells
+ * in random order, unless the cells on both sides are already connected. Each cell starts
+ * in its own set, and the sets are merged as the walls are opened.
+ */
+pub fn build_maze<G: MazeGrid, R: Rng + ?Sized>(grid: &G, rng: &mut R) {
+    let cells = grid.each_cell();
+    let index: HashMap<*const RefCell<G::Cell>, usize> = cells.iter()
+        .enumerate()
+        .map(|(i, cell)| (Rc::as_ptr(cell), i))
+        .collect();
+
+    // collects every wall once, from the cell which comes first
+    let mut walls = vec![];
+    for (i, cell) in cells.iter().enumerate() {
+        for neighbour in RefCell::borrow(cell).neighbours() {
+            let j = index[&neighbour.as_ptr()];
+            if i < j {
+                walls.push((i, j));
+            }
+        }
+    }
+    sample::shuffle(rng, &mut walls);
+
+    let mut sets: Vec<usize> = (0..cells.len()).collect();
+    for (i, j) in walls {
+        let (a, b) = (find(&mut sets, i), find(&mut sets, j));
+        // the walls are checked as they come, since the passages opened so far
+        // may rule some of them out, e.g. on the topologies where passages could cross
+        if a == b || !RefCell::borrow(&cells[i]).can_link_to(Rc::downgrade(&cells[j])) {
+            continue;
+        }
+        sets[a] = b;
+
+        RefCell::borrow_mut(&cells[i]).link_to(Rc::downgrade(&cells[j]));
+        RefCell::borrow_mut(&cells[j]).link_to(Rc::downgrade(&cells[i]));
+    }
+}
+
+
+// finds the set the element belongs to, shortening the path to it on the way
+pub(crate) fn find(sets: &mut [usize], mut i: usize) -> usize {
+    while sets[i] != i {
+        sets[i] = sets[sets[i]];
+        i = sets[i];
+    }
+    i
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::{grid, hex_grid};
+    use rand::thread_rng;
+    use crate::solver::distances::Distances;
+
+
+    #[test]
+    fn build() {
+        let mut grid = grid::Grid::new(12, 9);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        // a perfect maze is a spanning tree, i.e. it has exactly 'cells - 1' passages,
+        // and all cells are reachable
+        let links: usize = grid.each_cell().iter().map(|c| RefCell::borrow(c).links().len()).sum();
+        assert_eq!(2 * (12 * 9 - 1), links);
+
+        let distances = Distances::from(&grid, (0, 0));
+        assert!((0..9).all(|row| (0..12).all(|col| distances.get(row, col).is_some())));
+    }
+
+
+    #[test]
+    fn build_zeta() {
+        let mut grid = grid::Grid::new_zeta(10, 10);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        let links: usize = grid.each_cell().iter().map(|c| RefCell::borrow(c).links().len()).sum();
+        assert_eq!(2 * (10 * 10 - 1), links);
+    }
+
+
+    #[test]
+    fn build_hex() {
+        let mut grid = hex_grid::HexGrid::new(7, 6);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        let links: usize = grid.each_cell().iter().map(|c| RefCell::borrow(c).links().len()).sum();
+        assert_eq!(2 * (7 * 6 - 1), links);
+    }
+}
@@ -1,3 +1,53 @@
+use rand::Rng;
+use crate::Algo;
+use crate::maze::grid;
+use crate::maze::topology::MazeGrid;
+
 pub mod sidewinder;
 pub mod recursive_backtracking;
+pub mod kruskal;
+pub mod portals;
+pub mod one_way;
+pub mod nested;
+pub mod parallel;
+pub mod v1;
+
+
+/*
+ * Generates a maze on the grid using the selected algorithm.
+ */
+pub fn build_maze<R: Rng + ?Sized>(algo: &Algo, grid: &grid::Grid, rng: &mut R) {
+    match algo {
+        Algo::BinaryTree => panic!("Not yet implemented"),
+        Algo::Sidewinder => sidewinder::build_maze(grid, rng),
+        Algo::RecursiveBacktracking => recursive_backtracking::build_maze(grid, rng),
+        Algo::Prim => panic!("Not yet implemented"),
+        Algo::Kruskal => kruskal::build_maze(grid, rng),
+        Algo::Eller => panic!("Not yet implemented"),
+        Algo::HuntAndKill => panic!("Not yet implemented"),
+        Algo::AldousBroder => panic!("Not yet implemented"),
+        Algo::Wilson => panic!("Not yet implemented"),
+        Algo::RecursiveDivision => panic!("Not yet implemented"),
+        Algo::GrowingTree => panic!("Not yet implemented"),
+    };
+}
+
 
+/*
+ * Generates a maze on a grid of any topology, e.g. a user-supplied graph, using the selected
+ * algorithm. Only the algorithms relying on the neighbours of cells are available, as the ones
+ * walking the grid row by row need a rectangular grid.
+ */
+pub fn build_generic_maze<G: MazeGrid, R: Rng + ?Sized>(algo: &Algo, grid: &G, rng: &mut R) {
+    match algo {
+        Algo::BinaryTree | Algo::Sidewinder | Algo::Eller | Algo::RecursiveDivision =>
+            panic!("Only available on a rectangular grid"),
+        Algo::RecursiveBacktracking => recursive_backtracking::build_maze(grid, rng),
+        Algo::Kruskal => kruskal::build_maze(grid, rng),
+        Algo::Prim => panic!("Not yet implemented"),
+        Algo::HuntAndKill => panic!("Not yet implemented"),
+        Algo::AldousBroder => panic!("Not yet implemented"),
+        Algo::Wilson => panic!("Not yet implemented"),
+        Algo::GrowingTree => panic!("Not yet implemented"),
+    };
+}
@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use rand::Rng;
+use crate::Algo;
+use crate::algos;
+use crate::maze::nested_grid::{NestedGrid, Openings, Side};
+use crate::maze::topology::MazeCell;
+use crate::rng::sample;
+
+
+/*
+ * Generates the nested maze level by level using the selected algorithm. Once the passages
+ * of a level are carved, each of them gets a door leading through the sub-mazes of both cells
+ * it joins, placed at random along their common side on every deeper level. The sub-mazes are
+ * then created with the doors as their openings, and generated the same way.
+ */
+pub fn build_maze<R: Rng + ?Sized>(algo: &Algo, grid: &mut NestedGrid, rng: &mut R) {
+    algos::build_maze(algo, &grid.grid, rng);
+
+    if grid.depth == 1 {
+        return;
+    }
+
+    let (width, height) = (grid.width as usize, grid.height as usize);
+    let mut openings: Vec<Vec<Openings>> = vec![vec![vec![]; width]; height];
+
+    // passes the openings of the maze on to the cells on its sides
+    for (side, positions) in &grid.openings {
+        let k = positions[0] as usize;
+        let (row, col) = match side {
+            Side::North => (0, k),
+            Side::South => (height - 1, k),
+            Side::West => (k, 0),
+            Side::East => (k, width - 1),
+        };
+        openings[row][col].push((*side, positions[1..].to_vec()));
+    }
+
+    // places the doors of the passages leading to the right and the bottom cell
+    let levels = grid.depth as usize - 1;
+    for row in 0..height {
+        for col in 0..width {
+            let cell = Rc::clone(grid.grid.cells[row][col].as_ref().unwrap());
+            let c = RefCell::borrow(&cell);
+
+            if c.right.as_ref().is_some_and(|right| c.is_linked_to(Weak::clone(right))) {
+                let door: Vec<u16> = (0..levels).map(|_| sample::gen_index(rng, height) as u16).collect();
+                openings[row][col].push((Side::East, door.clone()));
+                openings[row][col + 1].push((Side::West, door));
+            }
+            if c.bottom.as_ref().is_some_and(|bottom| c.is_linked_to(Weak::clone(bottom))) {
+                let door: Vec<u16> = (0..levels).map(|_| sample::gen_index(rng, width) as u16).collect();
+                openings[row][col].push((Side::South, door.clone()));
+                openings[row + 1][col].push((Side::North, door));
+            }
+        }
+    }
+
+    grid.children = openings.into_iter().map(|row| row.into_iter().map(|openings| {
+        let mut child = NestedGrid::new(grid.width, grid.height, grid.depth - 1);
+        child.openings = openings;
+        child.init_grid();
+        build_maze(algo, &mut child, rng);
+        child
+    }).collect()).collect();
+}
@@ -0,0 +1,114 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::{cell, grid};
+use crate::maze::topology::MazeCell;
+use crate::rng::sample;
+
+
+/*
+ * Turns the fraction of the passages of the built maze into one-way ones, e.g. drop-down ledges,
+ * while keeping the exit reachable from every cell. The passages are picked among the ones on
+ * the paths leading to the exit, i.e. along a spanning tree of the cells reaching it, and are
+ * made to lead only towards it, so none of these paths gets broken. Returns the number of
+ * passages made one-way, which is the fraction of the passages of the tree, i.e. of the cells
+ * reaching the exit less one, while the passages outside of the tree stay two-way.
+ */
+pub fn make_one_way<R: Rng + ?Sized>(grid: &grid::Grid, exit: (u16, u16), fraction: f64, rng: &mut R) -> usize {
+    assert!((0.0..=1.0).contains(&fraction), "fraction must be between 0 and 1");
+
+    let exit = Rc::clone(grid.cells[exit.0 as usize][exit.1 as usize].as_ref()
+        .expect("exit must be a cell of the grid"));
+    let position = |c: &cell::CellStrongLink| {
+        let c = RefCell::borrow(c);
+        (c.row as usize, c.col as usize)
+    };
+
+    // walks backwards from the exit, along the passages leading to the cells already reached,
+    // and collects the passages used, which still lead both ways
+    let mut reached = vec![vec![false; grid.width as usize]; grid.height as usize];
+    reached[position(&exit).0][position(&exit).1] = true;
+    let mut queue = VecDeque::from([exit]);
+    let mut passages = vec![];
+    while let Some(to) = queue.pop_front() {
+        let candidates = {
+            let t = RefCell::borrow(&to);
+            let mut candidates = t.neighbours();
+            candidates.extend(t.portal.iter().cloned());
+            candidates
+        };
+
+        for from in candidates.iter().map(|n| n.upgrade().unwrap()) {
+            let (row, col) = position(&from);
+            if reached[row][col] || !RefCell::borrow(&from).is_linked_to(Rc::downgrade(&to)) {
+                continue;
+            }
+            reached[row][col] = true;
+            if RefCell::borrow(&to).is_linked_to(Rc::downgrade(&from)) {
+                passages.push((Rc::clone(&from), Rc::clone(&to)));
+            }
+            queue.push_back(from);
+        }
+    }
+
+    let count = (fraction * passages.len() as f64).round() as usize;
+    sample::shuffle(rng, &mut passages);
+    for (from, to) in &passages[..count] {
+        // keeps the link from the cell further from the exit only
+        RefCell::borrow_mut(to).unlink_from(Rc::downgrade(from));
+    }
+
+    count
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use crate::algos::{portals, recursive_backtracking};
+    use crate::solver::distances::Distances;
+
+
+    // checks if the exit is reachable from every cell of the grid
+    fn reaches_exit(grid: &grid::Grid, exit: (u16, u16)) -> bool {
+        grid.cells.iter().flatten().flatten().all(|cell| {
+            let c = RefCell::borrow(cell);
+            Distances::from(grid, (c.row, c.col)).get(exit.0, exit.1).is_some()
+        })
+    }
+
+
+    #[test]
+    fn one_way_keeps_exit_reachable() {
+        let mut grid = grid::Grid::new(8, 6);
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut thread_rng());
+        portals::place_portals(&grid, 2, &mut thread_rng());
+
+        let count = make_one_way(&grid, (5, 7), 0.5, &mut thread_rng());
+
+        // the paths to the exit form a spanning tree of 'cells - 1' passages, even with portals
+        assert_eq!(24, count);
+        assert!(reaches_exit(&grid, (5, 7)));
+        let links: usize = grid.cells.iter().flatten().flatten()
+            .map(|cell| RefCell::borrow(cell).links().len())
+            .sum();
+        assert_eq!(2 * (8 * 6 - 1 + 2) - count, links);
+    }
+
+
+    #[test]
+    fn one_way_all_passages() {
+        let mut grid = grid::Grid::new(5, 5);
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut thread_rng());
+
+        assert_eq!(24, make_one_way(&grid, (0, 0), 1.0, &mut thread_rng()));
+
+        // no cell can be left from the exit, though all of them lead to it
+        assert_eq!(((0, 0), 0), Distances::from(&grid, (0, 0)).max());
+        assert!(reaches_exit(&grid, (0, 0)));
+    }
+}
@@ -0,0 +1,258 @@
+use std::sync::Mutex;
+use std::thread;
+use rand::Rng;
+use crate::maze::passages::{Passages, EAST, SOUTH};
+use crate::rng::sample;
+use crate::rng::stable::{StableRng, Version};
+
+
+/*
+ * Generates a maze by splitting it into square tiles of 'tile_size' cells, which are carved
+ * independently on up to 'threads' threads using the recursive backtracking algorithm.
+ * The tiles are then stitched together along a random spanning tree over the tiles, hence
+ * the result is still a perfect maze.
+ *
+ * Each tile draws its random numbers from its own stable generator of the 'version', derived
+ * from 'seed' and the position of the tile, so the maze only depends on the seed, the version
+ * and the tile size, regardless of the number of threads used or the crate version.
+ */
+pub fn build_maze(width: u32, height: u32, tile_size: u32, seed: u64, version: Version, threads: usize)
+    -> Passages {
+    assert!(tile_size > 0, "tile size must be positive");
+
+    let mut passages = Passages::new(width, height);
+    if width == 0 || height == 0 {
+        return passages;
+    }
+
+    let tiles_x = width.div_ceil(tile_size);
+    let tiles_y = height.div_ceil(tile_size);
+
+    // every band holds one row of tiles, and is carved by a single thread at a time
+    let band_len = tile_size as usize * width as usize;
+    {
+        let bands = Mutex::new(passages.cells_mut().chunks_mut(band_len).enumerate());
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                scope.spawn(|| loop {
+                    let next = bands.lock().unwrap().next();
+                    let Some((ty, band)) = next else {
+                        break;
+                    };
+
+                    let th = band.len() / width as usize;
+                    for tx in 0..tiles_x {
+                        let x0 = (tx * tile_size) as usize;
+                        let tw = (tile_size as usize).min(width as usize - x0);
+
+                        let mut rng = tile_rng(version, seed, ty as u64 * tiles_x as u64 + tx as u64 + 1);
+                        carve_tile(band, width as usize, x0, tw, th, &mut rng);
+                    }
+                });
+            }
+        });
+    }
+
+    stitch_tiles(&mut passages, tile_size, tiles_x, tiles_y, &mut tile_rng(version, seed, 0));
+
+    passages
+}
+
+
+/*
+ * Returns the side of the square tiles, as pinned by the version, since the tiles shape the maze.
+ */
+pub fn tile_size(version: Version) -> u32 {
+    match version {
+        Version::V1 => 256,
+    }
+}
+
+
+// derives an independent random number generator for the specified stream from the seed
+fn tile_rng(version: Version, seed: u64, stream: u64) -> StableRng {
+    // SplitMix64 finaliser, spreading consecutive streams over the whole seed space
+    let mut z = seed ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    StableRng::new(version, z ^ (z >> 31))
+}
+
+
+// carves a single tile of 'tw x th' cells, starting at column 'x0' of the band
+fn carve_tile<R: Rng>(band: &mut [u8], width: usize, x0: usize, tw: usize, th: usize, rng: &mut R) {
+    let mut visited = vec![false; tw * th];
+
+    let start = sample::gen_index(rng, tw * th);
+    visited[start] = true;
+    let mut stack = vec![start];
+
+    while let Some(&current) = stack.last() {
+        let (row, col) = (current / tw, current % tw);
+
+        // gets all neighbours of the 'current' cell, which are not yet visited
+        let mut neighbours = [0; 4];
+        let mut n = 0;
+        if row > 0 && !visited[current - tw] {
+            neighbours[n] = current - tw;
+            n += 1;
+        }
+        if row + 1 < th && !visited[current + tw] {
+            neighbours[n] = current + tw;
+            n += 1;
+        }
+        if col > 0 && !visited[current - 1] {
+            neighbours[n] = current - 1;
+            n += 1;
+        }
+        if col + 1 < tw && !visited[current + 1] {
+            neighbours[n] = current + 1;
+            n += 1;
+        }
+
+        if n == 0 {
+            // if no unvisited neighbours remain, removes last cell from stack
+            stack.pop();
+            continue;
+        }
+
+        let neighbour = neighbours[sample::gen_index(rng, n)];
+
+        // the passage is stored in the upper or the left one of both cells
+        let first = current.min(neighbour);
+        let direction = if current.abs_diff(neighbour) == 1 { EAST } else { SOUTH };
+        band[(first / tw) * width + x0 + first % tw] |= direction;
+
+        visited[neighbour] = true;
+        stack.push(neighbour);
+    }
+}
+
+
+// joins the tiles with a single passage along each edge of a random spanning tree over
+// the tiles, found using the Kruskal's algorithm
+fn stitch_tiles<R: Rng>(passages: &mut Passages, tile_size: u32, tiles_x: u32, tiles_y: u32,
+                        rng: &mut R) {
+    // each edge joins a tile to its right (true) or bottom (false) neighbour
+    let mut edges = vec![];
+    for ty in 0..tiles_y {
+        for tx in 0..tiles_x {
+            if tx + 1 < tiles_x {
+                edges.push((tx, ty, true));
+            }
+            if ty + 1 < tiles_y {
+                edges.push((tx, ty, false));
+            }
+        }
+    }
+    sample::shuffle(rng, &mut edges);
+
+    let mut sets: Vec<usize> = (0..(tiles_x * tiles_y) as usize).collect();
+    fn find(sets: &mut [usize], mut i: usize) -> usize {
+        while sets[i] != i {
+            sets[i] = sets[sets[i]];
+            i = sets[i];
+        }
+        i
+    }
+
+    for (tx, ty, is_east) in edges {
+        let a = find(&mut sets, (ty * tiles_x + tx) as usize);
+        let b = if is_east {
+            find(&mut sets, (ty * tiles_x + tx + 1) as usize)
+        } else {
+            find(&mut sets, ((ty + 1) * tiles_x + tx) as usize)
+        };
+        if a == b {
+            continue;
+        }
+        sets[a] = b;
+
+        // opens the wall at a random position along the shared border
+        if is_east {
+            let y0 = ty * tile_size;
+            let row = y0 + sample::gen_index(rng, ((y0 + tile_size).min(passages.height) - y0) as usize) as u32;
+            passages.link_east(row, (tx + 1) * tile_size - 1);
+        } else {
+            let x0 = tx * tile_size;
+            let col = x0 + sample::gen_index(rng, ((x0 + tile_size).min(passages.width) - x0) as usize) as u32;
+            passages.link_south((ty + 1) * tile_size - 1, col);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+
+    // counts the cells reachable from the top left corner of the maze
+    fn count_reachable(passages: &Passages) -> usize {
+        let (w, h) = (passages.width, passages.height);
+        let mut visited = vec![false; (w * h) as usize];
+        let mut queue = VecDeque::from([(0u32, 0u32)]);
+        visited[0] = true;
+        let mut count = 0;
+
+        while let Some((r, c)) = queue.pop_front() {
+            count += 1;
+            let mut next = vec![];
+            if c + 1 < w && passages.is_linked_east(r, c) { next.push((r, c + 1)); }
+            if r + 1 < h && passages.is_linked_south(r, c) { next.push((r + 1, c)); }
+            if c > 0 && passages.is_linked_east(r, c - 1) { next.push((r, c - 1)); }
+            if r > 0 && passages.is_linked_south(r - 1, c) { next.push((r - 1, c)); }
+
+            for (nr, nc) in next {
+                if !visited[(nr * w + nc) as usize] {
+                    visited[(nr * w + nc) as usize] = true;
+                    queue.push_back((nr, nc));
+                }
+            }
+        }
+        count
+    }
+
+
+    #[test]
+    fn build_perfect_maze() {
+        let passages = build_maze(53, 37, 8, 42, Version::V1, 4);
+
+        assert_eq!(53 * 37 - 1, passages.count_passages());
+        assert_eq!(53 * 37, count_reachable(&passages));
+    }
+
+
+    #[test]
+    fn build_independent_of_threads() {
+        let one = build_maze(40, 30, 7, 7, Version::V1, 1);
+        let many = build_maze(40, 30, 7, 7, Version::V1, 5);
+
+        for row in 0..30 {
+            for col in 0..40 {
+                assert_eq!(one.is_linked_east(row, col), many.is_linked_east(row, col));
+                assert_eq!(one.is_linked_south(row, col), many.is_linked_south(row, col));
+            }
+        }
+    }
+
+
+    #[test]
+    fn tile_size_pinned() {
+        // changing the tile size of a released version would alter all of its mazes
+        assert_eq!(256, tile_size(Version::V1));
+    }
+
+
+    #[test]
+    fn build_depends_on_seed() {
+        let one = build_maze(20, 20, 5, 1, Version::V1, 2);
+        let two = build_maze(20, 20, 5, 2, Version::V1, 2);
+
+        let differs = (0..20).any(|row| (0..20).any(|col|
+            one.is_linked_east(row, col) != two.is_linked_east(row, col)));
+        assert!(differs);
+    }
+}
@@ -0,0 +1,105 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::{cell, grid};
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
+
+
+/*
+ * Places the pairs of portals into the built maze, each linking two distant cells, so that
+ * going through a portal takes a single step. Both cells of a pair are picked at random among
+ * the cells without a portal, and are never adjacent to each other. As the portals add
+ * shortcuts, the maze is no longer perfect. Returns the number of pairs placed, which is lower
+ * than requested, when the grid runs out of cells.
+ */
+pub fn place_portals<R: Rng + ?Sized>(grid: &grid::Grid, pairs: usize, rng: &mut R) -> usize {
+    let mut free: Vec<cell::CellStrongLink> = grid.each_cell().into_iter()
+        .filter(|cell| RefCell::borrow(cell).portal.is_none())
+        .collect();
+    sample::shuffle(rng, &mut free);
+
+    let mut placed = 0;
+    while placed < pairs && !free.is_empty() {
+        let a = free.remove(0);
+        let Some(index) = free.iter().position(|b| !is_adjacent(&a, b)) else {
+            continue;
+        };
+        let b = free.remove(index);
+
+        for (x, y) in [(&a, &b), (&b, &a)] {
+            let mut c = RefCell::borrow_mut(x);
+            c.portal = Some(Rc::downgrade(y));
+            c.link_to(Rc::downgrade(y));
+        }
+        placed += 1;
+    }
+
+    placed
+}
+
+
+// checks if the cells are neighbours of each other
+fn is_adjacent(a: &cell::CellStrongLink, b: &cell::CellStrongLink) -> bool {
+    let b = RefCell::borrow(b);
+    RefCell::borrow(a).neighbours().iter().any(|n| *RefCell::borrow(&n.upgrade().unwrap()) == *b)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, SeedableRng, rngs::StdRng};
+    use crate::algos::recursive_backtracking;
+    use crate::solver::distances::{self, Distances};
+
+
+    #[test]
+    fn portals_pair_up() {
+        let mut grid = grid::Grid::new(8, 8);
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut thread_rng());
+
+        assert_eq!(5, place_portals(&grid, 5, &mut thread_rng()));
+
+        let ends: Vec<_> = grid.each_cell().into_iter()
+            .filter(|cell| RefCell::borrow(cell).portal.is_some())
+            .collect();
+        assert_eq!(10, ends.len());
+        for cell in ends {
+            let other = RefCell::borrow(&cell).portal.as_ref().unwrap().upgrade().unwrap();
+            let back = RefCell::borrow(&other).portal.as_ref().unwrap().upgrade().unwrap();
+            assert!(Rc::ptr_eq(&cell, &back));
+            assert!(!is_adjacent(&cell, &other));
+            assert!(RefCell::borrow(&cell).is_linked_to(Rc::downgrade(&other)));
+        }
+    }
+
+
+    #[test]
+    fn portals_run_out_of_cells() {
+        // the cells in the corners of a 2x2 grid are all adjacent, except the diagonal ones
+        let mut grid = grid::Grid::new(2, 2);
+        grid.init_grid();
+
+        assert_eq!(2, place_portals(&grid, 3, &mut StdRng::seed_from_u64(1)));
+    }
+
+
+    #[test]
+    fn portals_count_as_one_step() {
+        let mut grid = grid::Grid::new(10, 1);
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut thread_rng());
+        assert_eq!(Some(9), distances::solution_length(&grid, (0, 0), (0, 9)));
+
+        let (a, b) = (grid.cells[0][0].as_ref().unwrap(), grid.cells[0][9].as_ref().unwrap());
+        for (x, y) in [(a, b), (b, a)] {
+            RefCell::borrow_mut(x).portal = Some(Rc::downgrade(y));
+            RefCell::borrow_mut(x).link_to(Rc::downgrade(y));
+        }
+
+        assert_eq!(Some(1), distances::solution_length(&grid, (0, 0), (0, 9)));
+        assert_eq!(((0, 5), 5), Distances::from(&grid, (0, 0)).max());
+    }
+}
@@ -1,23 +1,27 @@
 use std::cell::RefCell;
-use std::rc::Rc;
-use rand::{thread_rng, Rng, seq::SliceRandom};
-use crate::maze::{cell, grid};
+use std::rc::{Rc, Weak};
+use rand::Rng;
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
 
 
-pub fn build_maze(grid: &grid::Grid) {
-    let mut rng = thread_rng();
-
-    let mut stack = vec![Rc::downgrade(&grid.get_rand_cell().upgrade().unwrap()); 1];
+/*
+ * Generates the maze on a grid of any topology, as it only relies on the neighbours of cells.
+ */
+pub fn build_maze<G: MazeGrid, R: Rng + ?Sized>(grid: &G, rng: &mut R) {
+    let mut stack = vec![Rc::downgrade(&grid.get_rand_cell(rng).upgrade().unwrap())];
 
     while !stack.is_empty() {
         // picks the last cell from the stack
-        let mut current_cell = Rc::clone(&(stack[stack.len() - 1]).upgrade().unwrap());
+        let current_cell = Rc::clone(&(stack[stack.len() - 1]).upgrade().unwrap());
 
-        // gets all neighbours of the 'current_cell', which are not yet linked to any adjacent cell
+        // gets all neighbours of the 'current_cell', which are not yet linked to any adjacent cell,
+        // and can be reached without crossing another passage
         let mut neighbours = vec![];
         let n = RefCell::borrow(&current_cell).neighbours();
         for cell in n.iter() {
-            if !RefCell::borrow(cell.upgrade().as_ref().unwrap()).has_linked_cells() {
+            if !RefCell::borrow(cell.upgrade().as_ref().unwrap()).has_linked_cells()
+                && RefCell::borrow(&current_cell).can_link_to(Weak::clone(cell)) {
                 neighbours.push(Rc::downgrade(&cell.upgrade().unwrap()));
             }
         }
@@ -25,14 +29,14 @@ pub fn build_maze(grid: &grid::Grid) {
         if !neighbours.is_empty() {
             // picks one random neighbour from 'neighbours'
             let neighbour = Rc::clone(
-                &neighbours.choose(&mut rng).unwrap().upgrade().unwrap());
+                &sample::choose(rng, &neighbours).unwrap().upgrade().unwrap());
 
             // adds selected neighbour to the stack
             stack.push(Rc::downgrade(&neighbour));
 
             // links 'current_cell' to 'neighbour'
             {
-                let mut c = (&*current_cell).borrow_mut();
+                let mut c = (*current_cell).borrow_mut();
                 c.link_to(Rc::downgrade(&neighbour));
             }
 
@@ -49,6 +53,9 @@ pub fn build_maze(grid: &grid::Grid) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::maze::grid;
+    use rand::{thread_rng, SeedableRng, rngs::StdRng};
+    use crate::presenter::presenter::to_string;
     use crate::presenter::to_img::*;
 
 
@@ -57,11 +64,86 @@ mod tests {
         let mut grid = grid::Grid::new(15, 15);
         grid.init_grid();
 
-        build_maze(&grid);
+        build_maze(&grid, &mut thread_rng());
 
         let img = Image::new(20, 2);
         img.save(&grid, "test_backtracker.png");
 
-        assert!(true);
+        for row in &grid.cells {
+            for cell in row.iter() {
+                assert!(RefCell::borrow(cell.as_ref().unwrap()).has_linked_cells());
+            }
+        }
+    }
+
+
+    #[test]
+    fn build_toroidal() {
+        let mut grid = grid::Grid::new_toroidal(8, 6);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        // a perfect maze is a spanning tree, i.e. it has exactly 'cells - 1' passages
+        let links: usize = grid.cells.iter().flatten()
+            .map(|cell| RefCell::borrow(cell.as_ref().unwrap()).links().len())
+            .sum();
+        assert_eq!(2 * (8 * 6 - 1), links);
+    }
+
+
+    #[test]
+    fn build_zeta() {
+        let mut grid = grid::Grid::new_zeta(8, 6);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        let links: usize = grid.cells.iter().flatten()
+            .map(|cell| RefCell::borrow(cell.as_ref().unwrap()).links().len())
+            .sum();
+        assert_eq!(2 * (8 * 6 - 1), links);
+
+        // no two diagonal passages cross each other
+        for row in 0..5 {
+            for col in 0..7 {
+                let linked = |(r1, c1): (usize, usize), (r2, c2): (usize, usize)| {
+                    RefCell::borrow(grid.cells[r1][c1].as_ref().unwrap())
+                        .is_linked_to(Rc::downgrade(grid.cells[r2][c2].as_ref().unwrap()))
+                };
+                assert!(!(linked((row, col), (row + 1, col + 1))
+                    && linked((row, col + 1), (row + 1, col))));
+            }
+        }
+    }
+
+
+    #[test]
+    fn build_masked() {
+        let mask = crate::maze::mask::Mask::from_txt("tests/fixtures/masks/ring.txt").unwrap();
+        let mut grid = grid::Grid::with_mask(mask);
+        grid.init_grid();
+
+        build_maze(&grid, &mut thread_rng());
+
+        // the maze spans all cells of the ring around the hole in its centre
+        let links: usize = grid.cells.iter().flatten().flatten()
+            .map(|cell| RefCell::borrow(cell).links().len())
+            .sum();
+        assert_eq!(2 * (30 - 1), links);
+        assert!(grid.cells[2][3].is_none());
+    }
+
+
+    #[test]
+    fn build_seeded() {
+        let mazes: Vec<String> = (0..2).map(|_| {
+            let mut grid = grid::Grid::new(10, 10);
+            grid.init_grid();
+            build_maze(&grid, &mut StdRng::seed_from_u64(42));
+            to_string(&grid)
+        }).collect();
+
+        assert_eq!(mazes[0], mazes[1]);
     }
 }
\ No newline at end of file
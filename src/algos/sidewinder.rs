@@ -1,30 +1,65 @@
 use std::cell::RefCell;
 use std::rc::Rc;
-use rand::{thread_rng, Rng, seq::SliceRandom};
-use crate::maze::{cell, grid};
+use rand::Rng;
+use crate::algos::kruskal;
+use crate::maze::{cell, circ_grid, grid, level_grid};
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
 
 
-pub fn build_maze(grid: &grid::Grid) {
-    let mut rng = thread_rng();
-    for row in &grid.cells {
+/*
+ * Generates the maze on a rectangular grid. On a masked grid, the runs also end before
+ * the missing cells, and are closed only by the cells with a cell above them. The runs
+ * lacking such cells, e.g. below an overhang of the mask, are left apart at first, and
+ * once all rows are done, the parts of the maze are joined through random walls.
+ *
+ * Where the edges of the grid join each other, the passages are carved across them as well.
+ * The unbroken corridor may then lie in any row, with the rows below it closed upwards all
+ * the way round, and the runs may start anywhere along a row, going round its right edge.
+ * The edges joined upside down are left walled, as a run has to stay within its row.
+ */
+pub fn build_maze<R: Rng + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    let (height, width) = (grid.cells.len(), grid.width as usize);
+    let first_row = if grid.wraps_rows() { sample::gen_index(rng, height) } else { 0 };
+
+    for r in 0..height {
+        let row = &grid.cells[(first_row + r) % height];
+        let first_col = if grid.wraps_cols() && !grid.boundary.flips_rows() {
+            sample::gen_index(rng, width)
+        } else {
+            0
+        };
         let mut run = vec![];
 
-        for cell in row.iter() {
-            let cell = Rc::clone(&cell.as_ref().unwrap());
+        for c in 0..width {
+            let Some(cell) = &row[(first_col + c) % width] else {
+                continue;
+            };
+            let cell = Rc::clone(cell);
             run.push(Rc::clone(&cell));
 
-            let is_pace_to_close_run = {(&*cell).borrow().right.is_none() ||
-                (&*cell).borrow().top.is_some() && rng.gen::<bool>()};
+            // the last cell visited in the row closes the run, as on a grid whose right edge
+            // joins the left, it would otherwise lead back to the first run of the row
+            let is_pace_to_close_run = {c == width - 1 ||
+                (*cell).borrow().right.is_none() ||
+                r > 0 && sample::gen_bool(rng)};
 
             if is_pace_to_close_run {
-                let cell_ = Rc::clone(run.choose(&mut rng).as_ref().unwrap());
+                // prefers the cells of the run with a cell above, which all of them have,
+                // unless the grid is masked
+                let upward: Vec<_> = run.iter()
+                    .filter(|c| RefCell::borrow(c).top.is_some())
+                    .map(Rc::clone)
+                    .collect();
+                let candidates = if upward.is_empty() { &run } else { &upward };
+                let cell_ = Rc::clone(sample::choose(rng, candidates).as_ref().unwrap());
 
-                if (&*cell_).borrow().top.is_some() {
+                if r > 0 && (*cell_).borrow().top.is_some() {
                     // links cell to cell.top
                     {
-                        let mut c = (&*cell_).borrow_mut();
+                        let mut c = (*cell_).borrow_mut();
                         let n = &c.top.as_ref().unwrap().upgrade().unwrap();
-                        c.link_to(Rc::downgrade(&n));
+                        c.link_to(Rc::downgrade(n));
                         // (&*cell_).borrow_mut().link_to(Rc::downgrade(
                         //     &(&*cell_).borrow().top.as_ref().unwrap().upgrade().unwrap()
                         // ));
@@ -32,7 +67,7 @@ pub fn build_maze(grid: &grid::Grid) {
 
                     // calls the reverse 'link_to()' cell.top -> cell
                     RefCell::borrow_mut(
-                        &(&*cell_).borrow().top.as_ref().unwrap().upgrade().unwrap()
+                        &(*cell_).borrow().top.as_ref().unwrap().upgrade().unwrap()
                     ).link_to(Rc::downgrade(&cell_));
                 }
 
@@ -40,9 +75,9 @@ pub fn build_maze(grid: &grid::Grid) {
             } else {
                 // links cell to cell.right
                 {
-                    let mut c = (&*cell).borrow_mut();
+                    let mut c = (*cell).borrow_mut();
                     let n = &c.right.as_ref().unwrap().upgrade().unwrap();
-                    c.link_to(Rc::downgrade(&n));
+                    c.link_to(Rc::downgrade(n));
                     // (&*cell).borrow_mut().link_to(Rc::downgrade(
                     //         &(&*cell).borrow().right.as_ref().unwrap().upgrade().unwrap()
                     //     ));
@@ -50,18 +85,169 @@ pub fn build_maze(grid: &grid::Grid) {
 
                 // calls the reverse 'link_to()' cell.right -> cell
                 RefCell::borrow_mut(
-                    &(&*cell).borrow().right.as_ref().unwrap().upgrade().unwrap()
+                    &(*cell).borrow().right.as_ref().unwrap().upgrade().unwrap()
                 ).link_to(Rc::downgrade(&cell));
             }
         }
     }
+    join_parts(grid, rng);
+}
+
+
+// joins the parts of the maze, which are left apart by the runs that could not be closed,
+// by opening the walls between them in random order, unless both sides are already joined
+fn join_parts<R: Rng + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    // the runs of a full grid are all closed, except for those of the unbroken corridor
+    if grid.mask.is_none() {
+        return;
+    }
+
+    let width = grid.width as usize;
+    let index = |cell: &cell::CellStrongLink| {
+        let c = RefCell::borrow(cell);
+        c.row as usize * width + c.col as usize
+    };
+
+    // labels each cell with its part, found by following the passages
+    let mut parts = vec![None; width * grid.height as usize];
+    let mut count = 0;
+    for cell in grid.each_cell() {
+        if parts[index(&cell)].is_some() {
+            continue;
+        }
+        parts[index(&cell)] = Some(count);
+        let mut stack = vec![cell];
+        while let Some(cell) = stack.pop() {
+            for link in RefCell::borrow(&cell).links() {
+                let link = link.upgrade().unwrap();
+                if parts[index(&link)].is_none() {
+                    parts[index(&link)] = Some(count);
+                    stack.push(link);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    if count == 1 {
+        return;
+    }
+
+    let mut walls = vec![];
+    for cell in grid.each_cell() {
+        let neighbours: Vec<_> = {
+            let c = RefCell::borrow(&cell);
+            [&c.right, &c.bottom].into_iter().flatten().map(|n| n.upgrade().unwrap()).collect()
+        };
+        for neighbour in neighbours {
+            if parts[index(&cell)] != parts[index(&neighbour)] {
+                walls.push((Rc::clone(&cell), neighbour));
+            }
+        }
+    }
+    sample::shuffle(rng, &mut walls);
+
+    let mut sets: Vec<usize> = (0..count).collect();
+    for (a, b) in walls {
+        let (x, y) = (kruskal::find(&mut sets, parts[index(&a)].unwrap()),
+                      kruskal::find(&mut sets, parts[index(&b)].unwrap()));
+        if x == y {
+            continue;
+        }
+        sets[x] = y;
+
+        RefCell::borrow_mut(&a).link_to(Rc::downgrade(&b));
+        RefCell::borrow_mut(&b).link_to(Rc::downgrade(&a));
+    }
+}
+
+
+/*
+ * Generates the maze on a circular grid. The runs go clockwise along each ring, and are closed
+ * by a passage to the inner ring, as the rows are closed by a passage to the upper row.
+ */
+pub fn build_polar_maze<R: Rng + ?Sized>(grid: &circ_grid::CircGrid, rng: &mut R) {
+    for row in grid.cells.iter().skip(1) {
+        let mut run = vec![];
+
+        for (col, cell) in row.iter().enumerate() {
+            let cell = Rc::clone(cell.as_ref().unwrap());
+            run.push(Rc::clone(&cell));
+
+            // the last cell of the ring closes the run, as it would otherwise join
+            // the first run of the ring into a loop
+            let is_pace_to_close_run = col == row.len() - 1 || sample::gen_bool(rng);
+
+            if is_pace_to_close_run {
+                let cell_ = Rc::clone(sample::choose(rng, &run).unwrap());
+                let inward = (*cell_).borrow().inward.as_ref().unwrap().upgrade().unwrap();
+
+                // links cell to cell.inward, and calls the reverse 'link_to()'
+                (*cell_).borrow_mut().link_to(Rc::downgrade(&inward));
+                RefCell::borrow_mut(&inward).link_to(Rc::downgrade(&cell_));
+
+                run.clear();
+            } else {
+                let cw = (*cell).borrow().cw.as_ref().unwrap().upgrade().unwrap();
+
+                // links cell to cell.cw, and calls the reverse 'link_to()'
+                (*cell).borrow_mut().link_to(Rc::downgrade(&cw));
+                RefCell::borrow_mut(&cw).link_to(Rc::downgrade(&cell));
+            }
+        }
+    }
+}
+
+
+/*
+ * Generates the maze on a multi-level grid. The ground floor is built as usual, while on the
+ * upper floors the runs of the first row are closed by stairs down to the floor below.
+ */
+pub fn build_level_maze<R: Rng + ?Sized>(grid: &level_grid::LevelGrid, rng: &mut R) {
+    for row in grid.cells.iter().flatten() {
+        let mut run = vec![];
+
+        for (col, cell) in row.iter().enumerate() {
+            let cell = Rc::clone(cell.as_ref().unwrap());
+            run.push(Rc::clone(&cell));
+
+            let can_close_run = (*cell).borrow().north.is_some() || (*cell).borrow().down.is_some();
+            let is_pace_to_close_run = col == row.len() - 1 || can_close_run && sample::gen_bool(rng);
+
+            if is_pace_to_close_run {
+                let cell_ = Rc::clone(sample::choose(rng, &run).unwrap());
+                let next = {
+                    let c = (*cell_).borrow();
+                    c.north.as_ref().or(c.down.as_ref()).map(|n| n.upgrade().unwrap())
+                };
+
+                // links cell to cell.north or cell.down, and calls the reverse 'link_to()'
+                if let Some(next) = next {
+                    (*cell_).borrow_mut().link_to(Rc::downgrade(&next));
+                    RefCell::borrow_mut(&next).link_to(Rc::downgrade(&cell_));
+                }
+
+                run.clear();
+            } else {
+                let east = (*cell).borrow().east.as_ref().unwrap().upgrade().unwrap();
+
+                // links cell to cell.east, and calls the reverse 'link_to()'
+                (*cell).borrow_mut().link_to(Rc::downgrade(&east));
+                RefCell::borrow_mut(&east).link_to(Rc::downgrade(&cell));
+            }
+        }
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::presenter::presenter::print_to_console;
+    use std::rc::Weak;
+    use rand::{thread_rng, SeedableRng, rngs::StdRng};
+    use crate::presenter::presenter::{print_to_console, to_string};
+    use crate::maze::mask::Mask;
+    use crate::solver::distances::Distances;
 
 
     #[test]
@@ -69,10 +255,155 @@ mod tests {
         let mut grid = grid::Grid::new(5, 5);
         grid.init_grid();
 
-        build_maze(&grid);
+        build_maze(&grid, &mut thread_rng());
 
         print_to_console(&grid);
 
-        assert!(true);
+        for row in &grid.cells {
+            for cell in row.iter() {
+                assert!(RefCell::borrow(cell.as_ref().unwrap()).has_linked_cells());
+            }
+        }
+    }
+
+
+    #[test]
+    fn build_toroidal() {
+        let mut wraps = (false, false);
+        for seed in 0..10 {
+            let mut grid = grid::Grid::new_toroidal(8, 6);
+            grid.init_grid();
+
+            build_maze(&grid, &mut StdRng::seed_from_u64(seed));
+
+            // a perfect maze is a spanning tree, i.e. it has exactly 'cells - 1' passages,
+            // and all of its cells are reachable
+            let links: usize = grid.cells.iter().flatten()
+                .map(|cell| RefCell::borrow(cell.as_ref().unwrap()).links().len())
+                .sum();
+            assert_eq!(2 * (8 * 6 - 1), links);
+            assert_eq!(8 * 6, reachable(&grid, (0, 0)));
+
+            let linked = |(r1, c1): (usize, usize), (r2, c2): (usize, usize)| {
+                RefCell::borrow(grid.cells[r1][c1].as_ref().unwrap())
+                    .is_linked_to(Rc::downgrade(grid.cells[r2][c2].as_ref().unwrap()))
+            };
+            wraps.0 |= (0..6).any(|row| linked((row, 7), (row, 0)));
+            wraps.1 |= (0..8).any(|col| linked((5, col), (0, col)));
+        }
+
+        // the passages lead across the joined edges as well
+        assert_eq!((true, true), wraps);
+    }
+
+
+    #[test]
+    fn build_seeded() {
+        let mazes: Vec<String> = (0..2).map(|_| {
+            let mut grid = grid::Grid::new(10, 10);
+            grid.init_grid();
+            build_maze(&grid, &mut StdRng::seed_from_u64(42));
+            to_string(&grid)
+        }).collect();
+
+        assert_eq!(mazes[0], mazes[1]);
+    }
+
+
+    // counts the cells reachable from the specified one
+    fn reachable(grid: &grid::Grid, from: (u16, u16)) -> usize {
+        let distances = Distances::from(grid, from);
+        grid.each_cell().iter()
+            .filter(|cell| {
+                let c = RefCell::borrow(cell);
+                distances.get(c.row, c.col).is_some()
+            })
+            .count()
+    }
+
+
+    // checks if the maze is a spanning tree over all cells of the masked grid
+    fn assert_spans(grid: &grid::Grid, mask: &Mask) {
+        let cells = grid.each_cell();
+        let links: usize = cells.iter().map(|cell| RefCell::borrow(cell).links().len()).sum();
+        let first = (RefCell::borrow(&cells[0]).row, RefCell::borrow(&cells[0]).col);
+
+        assert_eq!(2 * (mask.count() - 1), links);
+        assert_eq!(mask.count(), reachable(grid, first));
+    }
+
+
+    #[test]
+    fn build_masked() {
+        // the hole in the ring overhangs the cells below it, whose run cannot be closed upwards
+        let mask = Mask::from_txt("tests/fixtures/masks/ring.txt").unwrap();
+
+        for seed in 0..20 {
+            let mut grid = grid::Grid::with_mask(mask.clone());
+            grid.init_grid();
+
+            build_maze(&grid, &mut StdRng::seed_from_u64(seed));
+
+            assert_spans(&grid, &mask);
+        }
     }
-}
\ No newline at end of file
+
+
+    #[test]
+    fn build_masked_random() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..20 {
+            let mut mask = Mask::new(12, 9);
+            for row in 0..9 {
+                for col in 0..12 {
+                    mask.set(row, col, sample::gen_bool(&mut rng));
+                }
+            }
+            mask.connect();
+
+            let mut grid = grid::Grid::with_mask(mask.clone());
+            grid.init_grid();
+
+            build_maze(&grid, &mut rng);
+
+            assert_spans(&grid, &mask);
+        }
+    }
+
+
+    #[test]
+    fn build_polar() {
+        let mut grid = circ_grid::CircGrid::new(6);
+        grid.init_grid();
+
+        build_polar_maze(&grid, &mut thread_rng());
+
+        // a perfect maze is a spanning tree, i.e. it has exactly 'cells - 1' passages
+        let links: usize = grid.cells.iter().flatten()
+            .map(|cell| RefCell::borrow(cell.as_ref().unwrap()).links().len())
+            .sum();
+        assert_eq!(2 * (grid.size() - 1), links);
+    }
+
+
+    #[test]
+    fn build_levels() {
+        let mut grid = level_grid::LevelGrid::new(3, 5, 4);
+        grid.init_grid();
+
+        build_level_maze(&grid, &mut thread_rng());
+
+        let cells: Vec<_> = grid.cells.iter().flatten().flatten().flatten().collect();
+        let links: usize = cells.iter().map(|cell| RefCell::borrow(cell).links().len()).sum();
+        assert_eq!(2 * (3 * 5 * 4 - 1), links);
+
+        // each upper floor is reached by stairs
+        for level in &grid.cells[1..] {
+            assert!(level[0].iter().any(|cell| {
+                let c = RefCell::borrow(cell.as_ref().unwrap());
+                c.is_linked_to(Weak::clone(c.down.as_ref().unwrap()))
+            }));
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::RngCore;
+use crate::maze::grid;
+use super::{can_link, find, link, neighbours, shuffle};
+
+
+/*
+ * Generates the maze by opening the walls between its cells in random order, unless the cells
+ * on both sides are already connected.
+ */
+pub fn build_maze<R: RngCore + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    let cells: Vec<_> = grid.cells.iter().flatten().flatten().map(Rc::clone).collect();
+    let width = grid.width as usize;
+    let mut index = vec![0; width * grid.height as usize];
+    for (i, cell) in cells.iter().enumerate() {
+        let c = RefCell::borrow(cell);
+        index[c.row as usize * width + c.col as usize] = i;
+    }
+
+    // collects every wall once, from the cell which comes first
+    let mut walls = vec![];
+    for (i, cell) in cells.iter().enumerate() {
+        for neighbour in neighbours(grid, cell) {
+            let j = {
+                let n = RefCell::borrow(&neighbour);
+                index[n.row as usize * width + n.col as usize]
+            };
+            if i < j {
+                walls.push((i, j));
+            }
+        }
+    }
+    shuffle(rng, &mut walls);
+
+    let mut sets: Vec<usize> = (0..cells.len()).collect();
+    for (i, j) in walls {
+        let (a, b) = (find(&mut sets, i), find(&mut sets, j));
+        if a == b || !can_link(grid, &cells[i], &cells[j]) {
+            continue;
+        }
+        sets[a] = b;
+
+        link(&cells[i], &cells[j]);
+    }
+}
@@ -0,0 +1,202 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::RngCore;
+use crate::Algo;
+use crate::maze::{cell, grid};
+use crate::maze::grid::Boundary;
+use crate::maze::topology::MazeCell;
+
+pub mod sidewinder;
+pub mod recursive_backtracking;
+pub mod kruskal;
+
+
+/*
+ * The algorithms of the stable generation pinned by 'stable::Version::V1'. They are frozen
+ * copies of the algorithms as released with the version, together with the way they sample
+ * the random numbers, pick the cells and list their neighbours, so the mazes stored with
+ * the version are recreated exactly. The grid only provides its size, its boundary and the cells
+ * kept by its mask, while the cells adjacent to each other are found here, with the edges joined
+ * as released with the version, hence the later changes to the grids do not alter the mazes
+ * either. Nothing in this module is ever edited, as any change would alter the stored mazes. The fixes and improvements go into the live algorithms,
+ * and reach the stable generation as a new version, with its own frozen copies.
+ */
+
+
+/*
+ * Generates a maze on the grid using the selected algorithm, as released with the version 1.
+ */
+pub fn build_maze<R: RngCore + ?Sized>(algo: &Algo, grid: &grid::Grid, rng: &mut R) {
+    match algo {
+        Algo::Sidewinder => sidewinder::build_maze(grid, rng),
+        Algo::RecursiveBacktracking => recursive_backtracking::build_maze(grid, rng),
+        Algo::Kruskal => kruskal::build_maze(grid, rng),
+        Algo::BinaryTree | Algo::Prim | Algo::Eller | Algo::HuntAndKill | Algo::AldousBroder
+        | Algo::Wilson | Algo::RecursiveDivision | Algo::GrowingTree =>
+            panic!("Not available in the version 1"),
+    };
+}
+
+
+// returns a uniformly distributed index lower than 'len', rejecting the values from
+// the incomplete last block, which would introduce a bias
+fn gen_index<R: RngCore + ?Sized>(rng: &mut R, len: usize) -> usize {
+    assert!(len > 0, "cannot sample from an empty range");
+
+    let len = len as u64;
+    let limit = u64::MAX - u64::MAX % len;
+    loop {
+        let value = rng.next_u64();
+        if value < limit {
+            return (value % len) as usize;
+        }
+    }
+}
+
+
+// returns a uniformly distributed bool
+fn gen_bool<R: RngCore + ?Sized>(rng: &mut R) -> bool {
+    rng.next_u64() >> 63 == 1
+}
+
+
+// returns a random element of the slice, or None if it is empty
+fn choose<'a, T, R: RngCore + ?Sized>(rng: &mut R, slice: &'a [T]) -> Option<&'a T> {
+    if slice.is_empty() {
+        return None;
+    }
+    Some(&slice[gen_index(rng, slice.len())])
+}
+
+
+// shuffles the slice in place using the Fisher-Yates algorithm
+fn shuffle<T, R: RngCore + ?Sized>(rng: &mut R, slice: &mut [T]) {
+    for i in (1..slice.len()).rev() {
+        slice.swap(i, gen_index(rng, i + 1));
+    }
+}
+
+
+// returns a random cell of the grid, drawing again when hitting a cell left out by the mask
+fn rand_cell<R: RngCore + ?Sized>(grid: &grid::Grid, rng: &mut R) -> cell::CellStrongLink {
+    loop {
+        let row = gen_index(rng, grid.height as usize);
+        let col = gen_index(rng, grid.width as usize);
+
+        if let Some(cell) = &grid.cells[row][col] {
+            return Rc::clone(cell);
+        }
+    }
+}
+
+
+// the directions towards the adjacent cells, as the changes of the row and the column
+const TOP: (i32, i32) = (-1, 0);
+const BOTTOM: (i32, i32) = (1, 0);
+const LEFT: (i32, i32) = (0, -1);
+const RIGHT: (i32, i32) = (0, 1);
+const TOP_LEFT: (i32, i32) = (-1, -1);
+const TOP_RIGHT: (i32, i32) = (-1, 1);
+const BOTTOM_LEFT: (i32, i32) = (1, -1);
+const BOTTOM_RIGHT: (i32, i32) = (1, 1);
+
+
+// returns whether the right edge of the grid joins the left, whether the bottom edge joins
+// the top, and whether the rows are flipped upside down, when crossing from the right edge
+// to the left, where the axes shorter than 3 cells are never wrapped
+fn wraps(grid: &grid::Grid) -> (bool, bool, bool) {
+    let (cols, rows, flips) = match grid.boundary {
+        Boundary::Box => (false, false, false),
+        Boundary::Cylinder => (true, false, false),
+        Boundary::Torus => (true, true, false),
+        Boundary::Mobius => (true, false, true),
+        Boundary::Klein => (true, true, true),
+    };
+
+    (cols && grid.width >= 3, rows && grid.height >= 3, flips)
+}
+
+
+// returns the adjacent cell in the direction, with the edges of the grid joined as set by
+// its boundary, or None if there is none, e.g. behind the edge, or left out by the mask
+fn neighbour(grid: &grid::Grid, cell: &cell::CellStrongLink, direction: (i32, i32)) -> Option<cell::CellStrongLink> {
+    let (height, width) = (grid.height as i32, grid.width as i32);
+    let (wraps_cols, wraps_rows, flips_rows) = wraps(grid);
+
+    let (mut row, mut col) = {
+        let c = RefCell::borrow(cell);
+        (c.row as i32 + direction.0, c.col as i32 + direction.1)
+    };
+    if wraps_cols && !(0..width).contains(&col) {
+        col = col.rem_euclid(width);
+        if flips_rows {
+            row = height - 1 - row;
+        }
+    }
+    if wraps_rows {
+        row = row.rem_euclid(height);
+    }
+
+    if 0 <= row && row < height && 0 <= col && col < width {
+        return grid.cells[row as usize][col as usize].as_ref().map(Rc::clone);
+    }
+    None
+}
+
+
+// returns the adjacent cells, the orthogonal ones first, then the diagonal ones
+fn neighbours(grid: &grid::Grid, cell: &cell::CellStrongLink) -> Vec<cell::CellStrongLink> {
+    let orthogonal = [TOP, BOTTOM, LEFT, RIGHT];
+    let diagonal = [TOP_LEFT, TOP_RIGHT, BOTTOM_LEFT, BOTTOM_RIGHT];
+    let directions = if grid.diagonal { [orthogonal, diagonal].concat() } else { orthogonal.to_vec() };
+
+    directions.into_iter().filter_map(|direction| neighbour(grid, cell, direction)).collect()
+}
+
+
+// checks if a passage between both adjacent cells would not cross the passage between
+// the two cells it squeezes in between, when leading diagonally
+fn can_link(grid: &grid::Grid, cell: &cell::CellStrongLink, other: &cell::CellStrongLink) -> bool {
+    if !grid.diagonal {
+        return true;
+    }
+
+    let corners = [(TOP_LEFT, TOP, LEFT),
+                   (TOP_RIGHT, TOP, RIGHT),
+                   (BOTTOM_LEFT, BOTTOM, LEFT),
+                   (BOTTOM_RIGHT, BOTTOM, RIGHT)];
+    for (diagonal, a, b) in corners {
+        let (Some(diagonal), Some(a), Some(b)) =
+            (neighbour(grid, cell, diagonal), neighbour(grid, cell, a), neighbour(grid, cell, b)) else {
+            continue;
+        };
+        if Rc::ptr_eq(&diagonal, other) {
+            return !is_linked(&a, &b);
+        }
+    }
+
+    true
+}
+
+
+// checks if the first cell is linked to the second one
+fn is_linked(a: &cell::CellStrongLink, b: &cell::CellStrongLink) -> bool {
+    RefCell::borrow(a).links().iter().any(|link| Rc::ptr_eq(&link.upgrade().unwrap(), b))
+}
+
+
+// links both cells to each other, the first one to the second one first
+fn link(a: &cell::CellStrongLink, b: &cell::CellStrongLink) {
+    RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+    RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+}
+
+
+// finds the set the element belongs to, shortening the path to it on the way
+fn find(sets: &mut [usize], mut i: usize) -> usize {
+    while sets[i] != i {
+        sets[i] = sets[sets[i]];
+        i = sets[i];
+    }
+    i
+}
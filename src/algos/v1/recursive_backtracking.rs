@@ -0,0 +1,29 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::RngCore;
+use crate::maze::grid;
+use crate::maze::topology::MazeCell;
+use super::{can_link, choose, link, neighbours, rand_cell};
+
+
+/*
+ * Generates the maze by walking from a random cell to the random neighbours not linked yet,
+ * and backtracking whenever there are none left.
+ */
+pub fn build_maze<R: RngCore + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    let mut stack = vec![rand_cell(grid, rng)];
+
+    while let Some(current) = stack.last().map(Rc::clone) {
+        let candidates: Vec<_> = neighbours(grid, &current).into_iter()
+            .filter(|n| !RefCell::borrow(n).has_linked_cells() && can_link(grid, &current, n))
+            .collect();
+
+        if let Some(next) = choose(rng, &candidates) {
+            let next = Rc::clone(next);
+            link(&current, &next);
+            stack.push(next);
+        } else {
+            stack.pop();
+        }
+    }
+}
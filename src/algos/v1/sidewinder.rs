@@ -0,0 +1,121 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::RngCore;
+use crate::maze::{cell, grid};
+use crate::maze::topology::MazeCell;
+use super::{choose, find, gen_bool, gen_index, link, neighbour, shuffle, wraps, BOTTOM, RIGHT, TOP};
+
+
+/*
+ * Generates the maze row by row, joining the cells of each row into runs, which are closed
+ * by a passage from a random cell of the run to the cell above it. Where the edges of
+ * the grid join each other, the first row and the start of the runs within each row are
+ * picked at random. The runs of a masked grid lacking a cell above them are left apart,
+ * and joined through random walls once all rows are done.
+ */
+pub fn build_maze<R: RngCore + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    let (height, width) = (grid.cells.len(), grid.width as usize);
+    let (wraps_cols, wraps_rows, flips_rows) = wraps(grid);
+    let first_row = if wraps_rows { gen_index(rng, height) } else { 0 };
+
+    for r in 0..height {
+        let row = &grid.cells[(first_row + r) % height];
+        let first_col = if wraps_cols && !flips_rows {
+            gen_index(rng, width)
+        } else {
+            0
+        };
+        let mut run = vec![];
+
+        for c in 0..width {
+            let Some(cell) = &row[(first_col + c) % width] else {
+                continue;
+            };
+            run.push(Rc::clone(cell));
+
+            let is_pace_to_close_run = c == width - 1 ||
+                neighbour(grid, cell, RIGHT).is_none() ||
+                r > 0 && gen_bool(rng);
+
+            if is_pace_to_close_run {
+                let upward: Vec<_> = run.iter()
+                    .filter(|cell| neighbour(grid, cell, TOP).is_some())
+                    .map(Rc::clone)
+                    .collect();
+                let candidates = if upward.is_empty() { &run } else { &upward };
+                let chosen = Rc::clone(choose(rng, candidates).unwrap());
+
+                if let Some(top) = neighbour(grid, &chosen, TOP).filter(|_| r > 0) {
+                    link(&chosen, &top);
+                }
+
+                run.clear();
+            } else {
+                link(cell, &neighbour(grid, cell, RIGHT).unwrap());
+            }
+        }
+    }
+
+    join_parts(grid, rng);
+}
+
+
+// joins the parts of a masked maze left apart, by opening the walls between them in random
+// order, unless both sides are already joined
+fn join_parts<R: RngCore + ?Sized>(grid: &grid::Grid, rng: &mut R) {
+    if grid.mask.is_none() {
+        return;
+    }
+
+    let cells: Vec<_> = grid.cells.iter().flatten().flatten().map(Rc::clone).collect();
+    let width = grid.width as usize;
+    let index = |cell: &cell::CellStrongLink| {
+        let c = RefCell::borrow(cell);
+        c.row as usize * width + c.col as usize
+    };
+
+    let mut parts = vec![None; width * grid.height as usize];
+    let mut count = 0;
+    for cell in &cells {
+        if parts[index(cell)].is_some() {
+            continue;
+        }
+        parts[index(cell)] = Some(count);
+        let mut stack = vec![Rc::clone(cell)];
+        while let Some(cell) = stack.pop() {
+            for link in RefCell::borrow(&cell).links() {
+                let link = link.upgrade().unwrap();
+                if parts[index(&link)].is_none() {
+                    parts[index(&link)] = Some(count);
+                    stack.push(link);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    if count == 1 {
+        return;
+    }
+
+    let mut walls = vec![];
+    for cell in &cells {
+        for other in [RIGHT, BOTTOM].into_iter().filter_map(|direction| neighbour(grid, cell, direction)) {
+            if parts[index(cell)] != parts[index(&other)] {
+                walls.push((Rc::clone(cell), other));
+            }
+        }
+    }
+    shuffle(rng, &mut walls);
+
+    let mut sets: Vec<usize> = (0..count).collect();
+    for (a, b) in walls {
+        let (x, y) = (find(&mut sets, parts[index(&a)].unwrap()), find(&mut sets, parts[index(&b)].unwrap()));
+        if x == y {
+            continue;
+        }
+        sets[x] = y;
+
+        link(&a, &b);
+    }
+}
@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+use crate::maze::grid;
+use crate::maze::topology::MazeCell;
+use crate::solver::distances;
+
+
+/*
+ * The metrics describing the structure of a maze, to be used when composing the fitness
+ * functions for the optimiser.
+ */
+
+
+/*
+ * Returns the length of the path from the top left to the bottom right cell of the maze.
+ * On a masked grid, these are the first and the last cell row by row.
+ */
+pub fn solution_length(grid: &grid::Grid) -> u32 {
+    let mut cells = grid.cells.iter().flatten().flatten().map(|cell| {
+        let c = RefCell::borrow(cell);
+        (c.row, c.col)
+    });
+    let start = cells.next().unwrap();
+    let goal = cells.next_back().unwrap_or(start);
+
+    distances::solution_length(grid, start, goal).unwrap_or(0)
+}
+
+
+/*
+ * Returns the number of dead ends, i.e. cells with a single passage.
+ */
+pub fn dead_ends(grid: &grid::Grid) -> usize {
+    count_cells(grid, |links| links == 1)
+}
+
+
+/*
+ * Returns the number of decision points, i.e. cells with three or more passages.
+ */
+pub fn decision_points(grid: &grid::Grid) -> usize {
+    count_cells(grid, |links| links >= 3)
+}
+
+
+// counts the cells, whose number of passages matches the predicate
+fn count_cells<P: Fn(usize) -> bool>(grid: &grid::Grid, predicate: P) -> usize {
+    grid.cells.iter().flatten().flatten()
+        .filter(|cell| predicate(RefCell::borrow(cell).links().len()))
+        .count()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::algos::sidewinder;
+
+
+    #[test]
+    fn metrics_perfect_maze() {
+        let mut grid = grid::Grid::new(8, 8);
+        grid.init_grid();
+        sidewinder::build_maze(&grid, &mut StdRng::seed_from_u64(5));
+
+        // the top row of a sidewinder maze is a single corridor ending with a dead end
+        assert!(dead_ends(&grid) >= 1);
+        assert!(solution_length(&grid) >= 14);
+    }
+
+
+    #[test]
+    fn metrics_empty_grid() {
+        let mut grid = grid::Grid::new(4, 4);
+        grid.init_grid();
+
+        assert_eq!(0, dead_ends(&grid));
+        assert_eq!(0, decision_points(&grid));
+        assert_eq!(0, solution_length(&grid));
+    }
+}
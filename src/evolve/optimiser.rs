@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::{cell, grid};
+use crate::maze::topology::MazeCell;
+use crate::rng::sample;
+use crate::solver::distances::Distances;
+
+
+/*
+ * Evolves the population of perfect mazes towards higher values of the fitness function,
+ * and returns the best maze found within the specified number of iterations.
+ *
+ * In each iteration, every maze is mutated by moving one of its walls, while keeping it
+ * a perfect maze. The mutated maze replaces the original one, unless it scores worse.
+ * Afterwards, the worst maze of the population is replaced by a copy of the best one.
+ */
+pub fn evolve<F, R>(population: Vec<grid::Grid>, fitness: F, iterations: usize, rng: &mut R) -> grid::Grid
+    where F: Fn(&grid::Grid) -> f64,
+          R: Rng + ?Sized,
+{
+    assert!(!population.is_empty(), "population must not be empty");
+
+    let mut scored: Vec<(f64, grid::Grid)> = population.into_iter()
+        .map(|grid| (fitness(&grid), grid))
+        .collect();
+
+    for _ in 0..iterations {
+        for member in scored.iter_mut() {
+            let child = member.1.clone();
+            mutate(&child, rng);
+
+            let score = fitness(&child);
+            if score >= member.0 {
+                *member = (score, child);
+            }
+        }
+
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let best = (scored[0].0, scored[0].1.clone());
+        *scored.last_mut().unwrap() = best;
+    }
+
+    scored.into_iter()
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap().1
+}
+
+
+/*
+ * Moves a random wall of the perfect maze, while keeping it perfect. A random passage
+ * is closed, which splits the maze into two parts, and these are joined again by opening
+ * a random wall between them. Returns false, if the maze has no passage to be closed.
+ */
+pub fn mutate<R: Rng + ?Sized>(grid: &grid::Grid, rng: &mut R) -> bool {
+    // collects every passage once, from the cell which comes first
+    let mut passages = vec![];
+    for cell in grid.cells.iter().flatten().flatten() {
+        for link in RefCell::borrow(cell).links() {
+            let link = link.upgrade().unwrap();
+            if position(cell) < position(&link) {
+                passages.push((Rc::clone(cell), link));
+            }
+        }
+    }
+
+    let Some((a, b)) = sample::choose(rng, &passages) else {
+        return false;
+    };
+    unlink(a, b);
+
+    // the cells remaining reachable from 'a' form one of the two parts
+    let part = Distances::from(grid, position(a));
+
+    let mut walls = vec![];
+    for cell in grid.cells.iter().flatten().flatten() {
+        let (row, col) = position(cell);
+        if part.get(row, col).is_none() {
+            continue;
+        }
+
+        for neighbour in RefCell::borrow(cell).neighbours() {
+            let neighbour = neighbour.upgrade().unwrap();
+            let (r, c) = position(&neighbour);
+            if part.get(r, c).is_none() && RefCell::borrow(cell).can_link_to(Rc::downgrade(&neighbour)) {
+                walls.push((Rc::clone(cell), neighbour));
+            }
+        }
+    }
+
+    // prefers any other wall than the one just closed, so the maze actually changes
+    if walls.len() > 1 {
+        walls.retain(|(x, y)| !(position(x) == position(a) && position(y) == position(b)));
+    }
+
+    let (x, y) = sample::choose(rng, &walls).unwrap();
+    link(x, y);
+
+    true
+}
+
+
+fn position(cell: &cell::CellStrongLink) -> (u16, u16) {
+    let c = RefCell::borrow(cell);
+    (c.row, c.col)
+}
+
+
+fn link(a: &cell::CellStrongLink, b: &cell::CellStrongLink) {
+    RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+    RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+}
+
+
+fn unlink(a: &cell::CellStrongLink, b: &cell::CellStrongLink) {
+    RefCell::borrow_mut(a).unlink_from(Rc::downgrade(b));
+    RefCell::borrow_mut(b).unlink_from(Rc::downgrade(a));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::algos::recursive_backtracking;
+    use crate::evolve::metrics;
+
+
+    fn build(seed: u64) -> grid::Grid {
+        let mut grid = grid::Grid::new(10, 10);
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut StdRng::seed_from_u64(seed));
+        grid
+    }
+
+
+    fn count_passages(grid: &grid::Grid) -> usize {
+        grid.cells.iter().flatten().flatten()
+            .map(|cell| RefCell::borrow(cell).links().len())
+            .sum::<usize>() / 2
+    }
+
+
+    #[test]
+    fn mutate_keeps_perfect_maze() {
+        let grid = build(1);
+        let mut rng = StdRng::seed_from_u64(2);
+
+        for _ in 0..50 {
+            assert!(mutate(&grid, &mut rng));
+
+            let distances = Distances::from(&grid, (0, 0));
+            assert_eq!(99, count_passages(&grid));
+            assert!((0..10).all(|r| (0..10).all(|c| distances.get(r, c).is_some())));
+        }
+    }
+
+
+    #[test]
+    fn evolve_does_not_get_worse() {
+        let population: Vec<grid::Grid> = (0..4).map(build).collect();
+        let initial = population.iter().map(metrics::solution_length).max().unwrap();
+
+        let best = evolve(population, |grid| metrics::solution_length(grid) as f64, 30,
+                          &mut StdRng::seed_from_u64(3));
+
+        assert!(metrics::solution_length(&best) >= initial);
+        assert_eq!(99, count_passages(&best));
+    }
+}
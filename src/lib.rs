@@ -55,15 +55,191 @@
 //! ```
 //!
 //! The above code will create an image of the maze with the same cell and wall size.
+//!
+//! Recreate the same maze, e.g. from a bug report or a shared puzzle, by passing a seed
+//! to [`build_with_seed`], or any random number generator to [`build_with_rng`]:
+//!
+//! ```ignore
+//! m::build_with_seed(Algo::Sidewinder, 42);
+//! ```
+//!
+//! Use [`build_stable`] instead, to get the same maze for the same seed across all crate versions,
+//! e.g. for saved levels:
+//!
+//! ```ignore
+//! use rs_mazegen::Version;
+//!
+//! m::build_stable(Algo::Sidewinder, 42, Version::V1);
+//! ```
+//!
+//! Craft mazes automatically, e.g. the hardest possible `30x30` puzzles, using [`optimise`]
+//! with a fitness function:
+//!
+//! ```ignore
+//! use rs_mazegen::metrics;
+//!
+//! m::init(30, 30);
+//! m::optimise(Algo::RecursiveBacktracking, 500, |grid| metrics::solution_length(grid) as f64);
+//! ```
+//!
+//! Add shortcuts between distant cells using [`place_portals`] after building the maze, whose
+//! paired ends are marked with matching colours and numbers in the image:
+//!
+//! ```ignore
+//! m::build();
+//! m::place_portals(4);
+//! ```
+//!
+//! Make some passages one-way using [`make_one_way`], while the exit stays reachable from
+//! everywhere:
+//!
+//! ```ignore
+//! m::build();
+//! m::make_one_way(0.2);
+//! ```
+//!
+//! Generate huge mazes (e.g. `20000x20000`) on all available threads using [`build_parallel`],
+//! which returns them in a compact format, and always produces the same maze for the same seed
+//! and version:
+//!
+//! ```ignore
+//! let passages = m::build_parallel(20000, 20000, 42, Version::V1);
+//! ```
+//!
+//! Create a seamlessly tileable maze, e.g. for repeating backgrounds, using [`init_toroidal`]
+//! instead of [`init`]:
+//!
+//! ```ignore
+//! m::init_toroidal(15, 50);
+//! m::build();
+//! ```
+//!
+//! Other ways of joining the edges, e.g. a Mobius strip or a Klein bottle, are set by
+//! [`init_with_boundary`], and their seams are marked in the image with matching colours:
+//!
+//! ```ignore
+//! m::init_with_boundary(15, 50, m::Boundary::Mobius);
+//! m::build();
+//! ```
+//!
+//! Let the passages also lead diagonally using [`init_zeta`], in which case they are carved
+//! by [`Algo::RecursiveBacktracking`] without ever crossing each other:
+//!
+//! ```ignore
+//! m::init_zeta(15, 50);
+//! m::build();
+//! ```
+//!
+//! Shape the maze like a letter or a logo using [`init_with_mask`], with a [`Mask`] loaded from
+//! a text file, whose `X` characters mark the cells left out:
+//!
+//! ```no_run
+//! # use rs_mazegen as m;
+//! m::init_with_mask(m::Mask::from_txt("logo.txt").unwrap());
+//! m::build();
+//! ```
+//!
+//! Or make the mask from the silhouette in an image, scaled to `40x30` cells, where the black
+//! or transparent pixels mark the cells left out. Only the largest part of the silhouette
+//! is kept, while [`Mask::from_image_bridged`] joins all of its parts instead:
+//!
+//! ```no_run
+//! # use rs_mazegen as m;
+//! m::init_with_mask(m::Mask::from_img("logo.png", 40, 30).unwrap());
+//! m::build();
+//! ```
+//!
+//! Precise outlines, e.g. polygons, circles, stars or rounded rectangles, possibly with holes,
+//! are given as a [`Shape`], which keeps the cells whose centres lie inside it:
+//!
+//! ```no_run
+//! # use rs_mazegen as m;
+//! use rs_mazegen::Shape;
+//!
+//! let ring = Shape::ring((20.0, 20.0), 20.0, 8.0);
+//! m::init_with_mask(m::Mask::from_shape(&ring, 40, 40));
+//! m::build();
+//! ```
+//!
+//! Write text with the built-in bitmap font using [`Mask::from_text`], whose letters are bridged
+//! so that a single maze fills them all, or [`Mask::from_text_negative`] to spell the text
+//! by the holes in the maze:
+//!
+//! ```no_run
+//! # use rs_mazegen as m;
+//! m::init_with_mask(m::Mask::from_text("HAPPY BIRTHDAY", 2));
+//! m::build();
+//! ```
+//!
+//! ## Other topologies
+//!
+//! Mazes of other shapes are built directly from their grids, using [`build_generic_maze`]
+//! and the [`Image`], e.g. a circular maze with `10` rings:
+//!
+//! ```no_run
+//! use rs_mazegen::{Algo, CircGrid, Image};
+//!
+//! let mut grid = CircGrid::new(10);
+//! grid.init_grid();
+//!
+//! rs_mazegen::build_generic_maze(&Algo::RecursiveBacktracking, &grid, &mut rand::thread_rng());
+//! Image::new(20, 2).save(&grid, "circular.png");
+//! ```
+//!
+//! The following topologies are available besides the [`Grid`]:
+//! - [`CircGrid`] - circular mazes, whose rings subdivide as they grow outwards, also built
+//!   with [`build_polar_maze`]
+//! - [`HexGrid`] - hex mazes, whose cells have six neighbours
+//! - [`TriGrid`] - delta mazes made of triangles, shaped as a rectangle, a large triangle,
+//!   or a hexagon
+//! - [`UpsilonGrid`] - upsilon mazes, whose octagons with eight neighbours alternate with squares
+//!   with four
+//! - [`WeaveGrid`] - weave mazes, whose passages may tunnel beneath the perpendicular ones,
+//!   built with [`Algo::RecursiveBacktracking`] or [`Algo::Kruskal`]
+//! - [`LevelGrid`] - multi-level mazes, whose floors are joined by stairs, also built
+//!   with [`build_level_maze`]
+//! - [`CubeGrid`] - mazes covering all six faces of a cube, drawn as its net
+//! - [`SphereGrid`] - spherical mazes of latitude bands, drawn as an equirectangular texture
+//! - [`VoronoiGrid`] - irregular mazes, whose cells are the Voronoi regions of random
+//!   or Poisson-disc sites
+//! - [`PenroseGrid`] - mazes on the aperiodic Penrose tiling of thin and thick rhombs
+//! - [`GraphGrid`] - mazes over any user-supplied graph, e.g. a road network
+//! - [`NestedGrid`] - nested mazes, whose every cell holds a sub-maze, built
+//!   with [`build_nested_maze`] and drawn with thinner walls at each deeper level
 
-
-use std::panic::panic_any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use rand::{thread_rng, Rng, SeedableRng, rngs::StdRng};
 
 mod maze;
 mod presenter;
 mod algos;
+mod solver;
+mod evolve;
+mod rng;
+
+pub use maze::grid::{Boundary, Grid};
+pub use maze::mask::Mask;
+pub use maze::shape::Shape;
+pub use maze::passages::Passages;
+pub use rng::stable::{StableRng, Version};
+pub use evolve::metrics;
+pub use maze::circ_grid::CircGrid;
+pub use maze::hex_grid::HexGrid;
+pub use maze::tri_grid::TriGrid;
+pub use maze::upsilon_grid::UpsilonGrid;
+pub use maze::weave_grid::WeaveGrid;
+pub use maze::level_grid::LevelGrid;
+pub use maze::cube_grid::CubeGrid;
+pub use maze::sphere_grid::SphereGrid;
+pub use maze::voronoi_grid::VoronoiGrid;
+pub use maze::penrose_grid::PenroseGrid;
+pub use maze::graph_grid::GraphGrid;
+pub use maze::nested_grid::{NestedGrid, Side};
+pub use algos::build_generic_maze;
+pub use algos::sidewinder::{build_polar_maze, build_level_maze};
+pub use algos::nested::build_maze as build_nested_maze;
+pub use presenter::to_img::Image;
 
 
 pub enum Algo {
@@ -82,14 +258,14 @@ pub enum Algo {
 
 
 thread_local!(static GRID: Rc<RefCell<Option<maze::grid::Grid>>> = Rc::new(RefCell::new(None)));
-thread_local!(static IMG: RefCell<Option<presenter::to_img::Image>> = RefCell::new(None));
+thread_local!(static IMG: RefCell<Option<presenter::to_img::Image>> = const { RefCell::new(None) });
 
 
 /// Returns the maze in its raw format - an object representing the maze internally
 /// in the crate.
-pub fn get_maze() -> Rc<RefCell<Option<maze::grid::Grid>>> {
+pub fn get_maze() -> Rc<RefCell<Option<Grid>>> {
     GRID.with(|grid| {
-        return Rc::clone(&grid);
+        Rc::clone(grid)
     })
 }
 
@@ -105,15 +281,118 @@ pub fn init(width: u16, height: u16) {
 }
 
 
+/// Initialises the geometry of a seamlessly tileable maze, whose right edge joins the left
+/// and whose bottom edge joins the top.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::init_toroidal(15, 10);
+/// maze::build();
+///
+/// assert_eq!(maze::Boundary::Torus, (&*maze::get_maze()).borrow().as_ref().unwrap().boundary);
+/// ```
+pub fn init_toroidal(width: u16, height: u16) {
+
+    GRID.with(|grid| {
+        *RefCell::borrow_mut(grid) = Some(maze::grid::Grid::new_toroidal(width, height));
+        RefCell::borrow_mut(grid).as_mut().unwrap().init_grid();
+    });
+
+}
+
+
+/// Initialises the geometry of a zeta maze, whose passages may also lead diagonally.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::init_zeta(15, 10);
+/// maze::build();
+///
+/// assert!((&*maze::get_maze()).borrow().as_ref().unwrap().diagonal);
+/// ```
+pub fn init_zeta(width: u16, height: u16) {
+
+    GRID.with(|grid| {
+        *RefCell::borrow_mut(grid) = Some(maze::grid::Grid::new_zeta(width, height));
+        RefCell::borrow_mut(grid).as_mut().unwrap().init_grid();
+    });
+
+}
+
+
+/// Initialises the maze geometry, with its edges joining each other as set by the `boundary`,
+/// e.g. into a cylinder, or a Mobius strip.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::init_with_boundary(15, 10, maze::Boundary::Klein);
+/// maze::build();
+///
+/// assert_eq!(maze::Boundary::Klein, (&*maze::get_maze()).borrow().as_ref().unwrap().boundary);
+/// ```
+pub fn init_with_boundary(width: u16, height: u16, boundary: Boundary) {
+
+    GRID.with(|grid| {
+        *RefCell::borrow_mut(grid) = Some(maze::grid::Grid::with_boundary(width, height, boundary));
+        RefCell::borrow_mut(grid).as_mut().unwrap().init_grid();
+    });
+
+}
+
+
+/// Initialises the geometry of a maze shaped by the `mask`, which leaves out the cells
+/// switched off. The maze is as large as the mask.
+///
+/// Panics if the mask has no cells, or if its parts do not touch each other, in which case
+/// they can be joined by [`Mask::connect`] first.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::init_with_mask(maze::Mask::parse("X....X\n......\n..XX..\n......\nX....X"));
+/// maze::build();
+///
+/// assert!((&*maze::get_maze()).borrow().as_ref().unwrap().cells[2][2].is_none());
+/// ```
+pub fn init_with_mask(mask: Mask) {
+
+    GRID.with(|grid| {
+        *RefCell::borrow_mut(grid) = Some(maze::grid::Grid::with_mask(mask));
+        RefCell::borrow_mut(grid).as_mut().unwrap().init_grid();
+    });
+
+}
+
+
 // Resets the maze, if maze is already initialised, otherwise initialises it.
 fn reinit() {
     GRID.with(|grid| {
         if RefCell::borrow(grid).is_none() {
             init(20, 20);
         } else {
-            let w = RefCell::borrow(grid).as_ref().unwrap().width;
-            let h = RefCell::borrow(grid).as_ref().unwrap().height;
-            init(w, h);
+            // keeps the size and the mode of the grid, but drops all of its cells
+            let mut fresh = {
+                let old = RefCell::borrow(grid);
+                let old = old.as_ref().unwrap();
+                maze::grid::Grid {
+                    mask: old.mask.clone(),
+                    cells: vec![],
+                    ..*old
+                }
+            };
+            fresh.init_grid();
+            *RefCell::borrow_mut(grid) = Some(fresh);
         }
     });
 
@@ -151,28 +430,236 @@ pub fn build() {
 /// assert_eq!(20u16, (&*maze::get_maze()).borrow().as_ref().unwrap().height);
 /// ```
 pub fn build_with(algo: Algo) {
+    build_with_rng(algo, &mut thread_rng());
+}
+
+
+/// Generates a reproducible maze using one of the selected algorithms. The same `seed`
+/// always generates the same maze for the same algorithm and maze size.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+/// use rs_mazegen::Algo;
+///
+/// maze::build_with_seed(Algo::Sidewinder, 42);
+///
+/// assert_eq!(20u16, (&*maze::get_maze()).borrow().as_ref().unwrap().height);
+/// ```
+pub fn build_with_seed(algo: Algo, seed: u64) {
+    build_with_rng(algo, &mut StdRng::seed_from_u64(seed));
+}
+
+
+/// Generates a maze using one of the selected algorithms, drawing all random numbers
+/// from the specified random number generator.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use rs_mazegen as maze;
+/// use rs_mazegen::Algo;
+///
+/// let mut rng = StdRng::seed_from_u64(42);
+/// maze::build_with_rng(Algo::RecursiveBacktracking, &mut rng);
+///
+/// assert_eq!(20u16, (&*maze::get_maze()).borrow().as_ref().unwrap().height);
+/// ```
+pub fn build_with_rng<R: Rng + ?Sized>(algo: Algo, rng: &mut R) {
+    reinit();
+
+    GRID.with(|grid| {
+        algos::build_maze(&algo, RefCell::borrow(grid).as_ref().unwrap(), rng);
+    });
+
+}
+
+
+/// Generates a maze, which is identical across crate versions for the same `algo`, maze size,
+/// `seed` and `version`. Hence a maze can be stored just as these values, and recreated later.
+///
+/// Unlike [`build_with_seed`], the random numbers are drawn from a [`StableRng`], and are
+/// consumed as pinned by the `version`, regardless of the version of the `rand` crate. Each
+/// version runs its own frozen copies of the algorithms, which also find the adjacent cells
+/// themselves, including across the joined edges, hence the later changes to the algorithms
+/// or the grids used by [`build_with`] never alter its mazes. Besides the `seed`, the maze depends
+/// only on the size, the [`Boundary`] and the cells kept by the [`Mask`] of the current grid.
+/// The version 1 provides [`Algo::Sidewinder`], [`Algo::RecursiveBacktracking`]
+/// and [`Algo::Kruskal`].
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+/// use rs_mazegen::{Algo, Version};
+///
+/// maze::init(12, 8);
+/// maze::build_stable(Algo::RecursiveBacktracking, 42, Version::V1);
+///
+/// assert_eq!(8u16, (&*maze::get_maze()).borrow().as_ref().unwrap().height);
+/// ```
+pub fn build_stable(algo: Algo, seed: u64, version: Version) {
+    reinit();
+    let mut rng = StableRng::new(version, seed);
+
+    GRID.with(|grid| {
+        let grid = RefCell::borrow(grid);
+        match version {
+            Version::V1 => algos::v1::build_maze(&algo, grid.as_ref().unwrap(), &mut rng),
+        }
+    });
+}
+
+
+/// Places the pairs of portals into the generated maze, each linking two distant cells, so that
+/// going through a portal counts as a single step, e.g. in [`metrics::solution_length`]. Both
+/// ends of each pair are marked with the same colour and number in the image. Returns the number
+/// of pairs placed, which is lower than requested, when the maze runs out of cells.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::build();
+///
+/// assert_eq!(3, maze::place_portals(3));
+/// ```
+pub fn place_portals(pairs: usize) -> usize {
+    place_portals_with_rng(pairs, &mut thread_rng())
+}
+
+
+/// Places the pairs of portals into the generated maze, drawing all random numbers from
+/// the specified random number generator, see [`place_portals`].
+pub fn place_portals_with_rng<R: Rng + ?Sized>(pairs: usize, rng: &mut R) -> usize {
+    GRID.with(|grid| {
+        if RefCell::borrow(grid).is_none() {
+            init(20, 20);
+        }
+        algos::portals::place_portals(RefCell::borrow(grid).as_ref().unwrap(), pairs, rng)
+    })
+}
+
+
+/// Turns the `fraction` of the passages of the generated maze into one-way ones, e.g. drop-down
+/// ledges, which are drawn with arrows in the image. The exit is the last cell of the maze, row
+/// by row, i.e. the bottom right cell unless the mask leaves it out, and remains reachable from
+/// every cell.
+///
+/// The passages are only picked along a spanning tree of the paths leading to the exit, which has
+/// a passage less than the maze has cells. Hence the number of passages made one-way, which is
+/// returned, is the `fraction` of the cells less one, rounded to the nearest whole number, while
+/// the further passages, e.g. the loops closed by portals, stay two-way.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+///
+/// maze::init(10, 10);
+/// maze::build();
+///
+/// // the spanning tree has a passage less than the maze has cells
+/// let passages = 10 * 10 - 1;
+/// assert_eq!((passages as f64 / 3.0).round() as usize, maze::make_one_way(1.0 / 3.0));
+/// ```
+pub fn make_one_way(fraction: f64) -> usize {
+    make_one_way_with_rng(fraction, &mut thread_rng())
+}
+
+
+/// Turns the `fraction` of the passages of the generated maze into one-way ones, drawing all
+/// random numbers from the specified random number generator, see [`make_one_way`].
+pub fn make_one_way_with_rng<R: Rng + ?Sized>(fraction: f64, rng: &mut R) -> usize {
+    GRID.with(|grid| {
+        if RefCell::borrow(grid).is_none() {
+            init(20, 20);
+        }
+        let grid = RefCell::borrow(grid);
+        let grid = grid.as_ref().unwrap();
+
+        let exit = grid.cells.iter().flatten().flatten().next_back().unwrap();
+        let exit = (RefCell::borrow(exit).row, RefCell::borrow(exit).col);
+        algos::one_way::make_one_way(grid, exit, fraction, rng)
+    })
+}
+
+
+/// Optimises the maze towards higher values of the `fitness` function. A population of mazes
+/// is generated using the selected algorithm, and evolved by moving their walls for the specified
+/// number of `iterations`. The best maze found is stored as the current maze.
+///
+/// The [`metrics`] module provides common metrics to compose the fitness function from.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+/// use rs_mazegen::Algo;
+/// use rs_mazegen::metrics;
+///
+/// maze::init(10, 10);
+/// maze::optimise(Algo::RecursiveBacktracking, 20, |grid| metrics::dead_ends(grid) as f64);
+///
+/// assert_eq!(10u16, (&*maze::get_maze()).borrow().as_ref().unwrap().height);
+/// ```
+pub fn optimise<F: Fn(&Grid) -> f64>(algo: Algo, iterations: usize, fitness: F) {
+    optimise_with_rng(algo, iterations, fitness, &mut thread_rng());
+}
+
+
+/// Optimises the maze towards higher values of the `fitness` function, drawing all random numbers
+/// from the specified random number generator, see [`optimise`].
+pub fn optimise_with_rng<F, R>(algo: Algo, iterations: usize, fitness: F, rng: &mut R)
+    where F: Fn(&Grid) -> f64,
+          R: Rng + ?Sized,
+{
     reinit();
 
     GRID.with(|grid| {
-        // let g = RefCell::borrow(grid).as_ref().unwrap();
-
-        match algo {
-            Algo::BinaryTree => panic!("Not yet implemented"),
-            Algo::Sidewinder => algos::sidewinder::build_maze(
-                RefCell::borrow(grid).as_ref().unwrap()),
-            Algo::RecursiveBacktracking => algos::recursive_backtracking::build_maze(
-                RefCell::borrow(grid).as_ref().unwrap()),
-            Algo::Prim => panic!("Not yet implemented"),
-            Algo::Kruskal => panic!("Not yet implemented"),
-            Algo::Eller => panic!("Not yet implemented"),
-            Algo::HuntAndKill => panic!("Not yet implemented"),
-            Algo::AldousBroder => panic!("Not yet implemented"),
-            Algo::Wilson => panic!("Not yet implemented"),
-            Algo::RecursiveDivision => panic!("Not yet implemented"),
-            Algo::GrowingTree => panic!("Not yet implemented"),
-        };
+        let population = (0..OPTIMISER_POPULATION).map(|_| {
+            // the current maze is freshly initialised, hence its copies have no passages yet
+            let g = RefCell::borrow(grid).as_ref().unwrap().clone();
+            algos::build_maze(&algo, &g, rng);
+            g
+        }).collect();
+
+        let best = evolve::optimiser::evolve(population, fitness, iterations, rng);
+        *RefCell::borrow_mut(grid) = Some(best);
     });
+}
+
+
+// the number of mazes evolved by [`optimise`]
+const OPTIMISER_POPULATION: usize = 16;
+
 
+/// Generates a random maze of any size in parallel, by carving square tiles on all available
+/// threads and stitching them together into a single perfect maze.
+///
+/// The maze is returned in a compact format, instead of being stored as the current maze, as
+/// very large mazes would not fit into memory otherwise. The result only depends on the `seed`
+/// and the `version`, which pins the random numbers drawn and the size of the tiles, like
+/// in [`build_stable`], regardless of the number of threads and the crate version. Use
+/// [`Passages::to_grid`] to convert smaller mazes.
+///
+/// # Examples
+///
+/// ```
+/// use rs_mazegen as maze;
+/// use rs_mazegen::Version;
+///
+/// let passages = maze::build_parallel(500, 300, 42, Version::V1);
+///
+/// assert_eq!(500 * 300 - 1, passages.count_passages());
+/// ```
+pub fn build_parallel(width: u32, height: u32, seed: u64, version: Version) -> Passages {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let tile_size = algos::parallel::tile_size(version);
+    algos::parallel::build_maze(width, height, tile_size, seed, version, threads)
 }
 
 
@@ -234,3 +721,120 @@ pub fn save_as_img(filename: &str) {
     });
 
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use presenter::presenter::to_string;
+
+
+    // builds a maze and draws it the same way as the golden mazes are stored
+    fn build_golden(algo: Algo, width: u16, height: u16, seed: u64) -> String {
+        init(width, height);
+        rebuild_golden(algo, seed)
+    }
+
+
+    // builds a maze on the current grid, e.g. a wrapped or a masked one, and draws it
+    // the same way as the golden mazes are stored
+    fn rebuild_golden(algo: Algo, seed: u64) -> String {
+        build_stable(algo, seed, Version::V1);
+
+        GRID.with(|grid| to_string(RefCell::borrow(grid).as_ref().unwrap()))
+    }
+
+
+    #[test]
+    fn golden_recursive_backtracking_v1() {
+        assert_eq!(include_str!("../tests/fixtures/golden/recursive_backtracking_v1_12x8_42.txt"),
+                   build_golden(Algo::RecursiveBacktracking, 12, 8, 42));
+    }
+
+
+    #[test]
+    fn golden_sidewinder_v1() {
+        assert_eq!(include_str!("../tests/fixtures/golden/sidewinder_v1_12x8_42.txt"),
+                   build_golden(Algo::Sidewinder, 12, 8, 42));
+    }
+
+
+    #[test]
+    fn golden_kruskal_v1() {
+        assert_eq!(include_str!("../tests/fixtures/golden/kruskal_v1_12x8_42.txt"),
+                   build_golden(Algo::Kruskal, 12, 8, 42));
+    }
+
+
+    #[test]
+    fn golden_sidewinder_torus_v1() {
+        init_with_boundary(12, 8, Boundary::Torus);
+
+        assert_eq!(include_str!("../tests/fixtures/golden/sidewinder_v1_torus_12x8_42.txt"),
+                   rebuild_golden(Algo::Sidewinder, 42));
+    }
+
+
+    #[test]
+    fn golden_kruskal_klein_v1() {
+        init_with_boundary(12, 8, Boundary::Klein);
+
+        assert_eq!(include_str!("../tests/fixtures/golden/kruskal_v1_klein_12x8_42.txt"),
+                   rebuild_golden(Algo::Kruskal, 42));
+    }
+
+
+    #[test]
+    fn golden_sidewinder_ring_v1() {
+        init_with_mask(Mask::parse(include_str!("../tests/fixtures/masks/ring.txt")));
+
+        assert_eq!(include_str!("../tests/fixtures/golden/sidewinder_v1_ring_42.txt"),
+                   rebuild_golden(Algo::Sidewinder, 42));
+    }
+
+
+    #[test]
+    fn golden_recursive_backtracking_ring_v1() {
+        init_with_mask(Mask::parse(include_str!("../tests/fixtures/masks/ring.txt")));
+
+        assert_eq!(include_str!("../tests/fixtures/golden/recursive_backtracking_v1_ring_42.txt"),
+                   rebuild_golden(Algo::RecursiveBacktracking, 42));
+    }
+
+
+    #[test]
+    fn one_way_seeded() {
+        let mazes: Vec<String> = (0..2).map(|_| {
+            init(8, 8);
+            build_with_seed(Algo::RecursiveBacktracking, 3);
+            make_one_way_with_rng(0.5, &mut StdRng::seed_from_u64(1));
+
+            GRID.with(|grid| to_string(RefCell::borrow(grid).as_ref().unwrap()))
+        }).collect();
+
+        assert_eq!(mazes[0], mazes[1]);
+    }
+
+
+    #[test]
+    fn optimise_seeded() {
+        let mazes: Vec<String> = (0..2).map(|_| {
+            init(8, 8);
+            optimise_with_rng(Algo::RecursiveBacktracking, 10,
+                              |grid| evolve::metrics::dead_ends(grid) as f64, &mut StdRng::seed_from_u64(5));
+
+            GRID.with(|grid| to_string(RefCell::borrow(grid).as_ref().unwrap()))
+        }).collect();
+
+        assert_eq!(mazes[0], mazes[1]);
+    }
+
+
+    #[test]
+    fn golden_parallel_v1() {
+        let grid = algos::parallel::build_maze(24, 16, 8, 42, Version::V1, 2).to_grid();
+
+        assert_eq!(include_str!("../tests/fixtures/golden/parallel_v1_24x16_8_42.txt"),
+                   to_string(&grid));
+    }
+}
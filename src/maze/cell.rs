@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::rc::{Rc, Weak};
-use std::cmp::{PartialEq, Eq};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
 
 
 pub type CellWeakLink = Weak<RefCell<Cell>>;
@@ -8,7 +9,8 @@ pub type CellStrongLink = Rc<RefCell<Cell>>;
 
 
 /*
- * The Cell class used to represent a cell in the maze.
+ * The Cell class used to represent a cell in the maze. A passage between two cells leads
+ * both ways, when both of them are linked to each other, and only one way otherwise.
  */
 pub struct Cell {
     pub row: u16,
@@ -20,8 +22,25 @@ pub struct Cell {
     pub bottom: Option<CellWeakLink>,
     pub left: Option<CellWeakLink>,
     pub right: Option<CellWeakLink>,
+
+    // only set on the grids allowing diagonal passages
+    pub top_left: Option<CellWeakLink>,
+    pub top_right: Option<CellWeakLink>,
+    pub bottom_left: Option<CellWeakLink>,
+    pub bottom_right: Option<CellWeakLink>,
+
+    // a distant cell, which is linked to this one through a portal
+    pub portal: Option<CellWeakLink>,
 }
 
+/*
+ * Checks if there is a passage between both cells, leading either way.
+ */
+pub fn is_open(a: &CellStrongLink, b: &CellStrongLink) -> bool {
+    RefCell::borrow(a).is_linked_to(Rc::downgrade(b)) || RefCell::borrow(b).is_linked_to(Rc::downgrade(a))
+}
+
+
 impl PartialEq for Cell {
     fn eq(&self, other: &Self) -> bool {
         self.row == other.row && self.col == other.col
@@ -41,46 +60,30 @@ impl Cell {
             bottom: None,
             left: None,
             right: None,
-        }
-    }
 
+            top_left: None,
+            top_right: None,
+            bottom_left: None,
+            bottom_right: None,
 
-    /*
-     * Creates a link to the specified adjacent cell.
-     */
-    pub fn link_to(&mut self, cell: CellWeakLink) {
-        self.links.push(Rc::downgrade(&cell.upgrade().unwrap()));
+            portal: None,
+        }
     }
+}
 
 
-    /*
-     * Checks if it is linked to any adjacent cell in the maze.
-     */
-    pub fn has_linked_cells(&self) -> bool {
-        !self.links.is_empty()
+impl MazeCell for Cell {
+    fn link_list(&self) -> &Vec<CellWeakLink> {
+        &self.links
     }
 
 
-    /*
-     * Checks if it is linked to the specified cell.
-     */
-    pub fn is_linked_to(&self, cell: CellWeakLink) -> bool {
-        // self.links.contains(&cell) // PartialEq not implemented on Weak<RefCell<Cell>>
-
-        for link in &self.links {
-            if *(&*link.upgrade().unwrap()).borrow() == *(&*cell.upgrade().unwrap()).borrow() {
-                return true;
-            }
-        }
-
-        false
+    fn link_list_mut(&mut self) -> &mut Vec<CellWeakLink> {
+        &mut self.links
     }
 
 
-    /*
-     * Returns a list of all adjacent neighbours.
-     */
-    pub fn neighbours(&self) -> Vec<CellWeakLink> {
+    fn neighbours(&self) -> Vec<CellWeakLink> {
         let mut lst: Vec<CellWeakLink> = vec![];
 
         if let Some(cell) = &self.top {
@@ -95,11 +98,39 @@ impl Cell {
         if let Some(cell) = &self.right {
             lst.push(Rc::downgrade(&cell.upgrade().unwrap()));
         }
+        for cell in [&self.top_left, &self.top_right, &self.bottom_left, &self.bottom_right]
+            .into_iter().flatten() {
+            lst.push(Rc::downgrade(&cell.upgrade().unwrap()));
+        }
 
         lst
-
     }
 
+
+    /*
+     * Checks if a passage to the specified adjacent cell would not cross another passage.
+     * A diagonal passage crosses the passage between the two cells it squeezes in between,
+     * e.g. the one leading to the top right cell crosses the one from the top to the right cell.
+     */
+    fn can_link_to(&self, cell: CellWeakLink) -> bool {
+        let cell = cell.upgrade().unwrap();
+
+        let corners = [(&self.top_left, &self.top, &self.left),
+                       (&self.top_right, &self.top, &self.right),
+                       (&self.bottom_left, &self.bottom, &self.left),
+                       (&self.bottom_right, &self.bottom, &self.right)];
+
+        for (diagonal, a, b) in corners {
+            let (Some(diagonal), Some(a), Some(b)) = (diagonal, a, b) else {
+                continue;
+            };
+            if *diagonal.upgrade().unwrap().borrow() == *cell.borrow() {
+                return !RefCell::borrow(&a.upgrade().unwrap()).is_linked_to(Weak::clone(b));
+            }
+        }
+
+        true
+    }
 }
 
 
@@ -109,6 +140,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn cell_link_to() {
         let cell1 = Rc::new(RefCell::new(Cell::new(2, 1)));
         let cell2 = Rc::new(RefCell::new(Cell::new(2, 2)));
@@ -124,6 +156,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn cell_is_not_linked_to() {
         let cell1 = Rc::new(RefCell::new(Cell::new(2, 1)));
         let cell2 = Rc::new(RefCell::new(Cell::new(2, 2)));
@@ -137,6 +170,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn cell_right_left() {
         let cell1 = Rc::new(RefCell::new(Cell::new(2, 1)));
         let cell2 = Rc::new(RefCell::new(Cell::new(2, 2)));
@@ -150,6 +184,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn cell_neighbours() {
         let cell1 = Rc::new(RefCell::new(Cell::new(2, 1)));
         let cell2 = Rc::new(RefCell::new(Cell::new(2, 2)));
@@ -163,4 +198,39 @@ mod tests {
 
         assert_eq!(true, !neighbours.is_empty());
     }
+
+
+    #[test]
+    fn cell_unlink_from() {
+        let cell1 = Rc::new(RefCell::new(Cell::new(2, 1)));
+        let cell2 = Rc::new(RefCell::new(Cell::new(2, 2)));
+        let cell3 = Rc::new(RefCell::new(Cell::new(3, 1)));
+
+        (*cell1).borrow_mut().link_to(Rc::downgrade(&cell2));
+        (*cell1).borrow_mut().link_to(Rc::downgrade(&cell3));
+        (*cell1).borrow_mut().unlink_from(Rc::downgrade(&cell2));
+
+        assert!(!(*cell1).borrow().is_linked_to(Rc::downgrade(&cell2)));
+        assert!((*cell1).borrow().is_linked_to(Rc::downgrade(&cell3)));
+    }
+
+
+    #[test]
+    fn cell_diagonals_do_not_cross() {
+        let cell = Rc::new(RefCell::new(Cell::new(1, 1)));
+        let top = Rc::new(RefCell::new(Cell::new(0, 1)));
+        let right = Rc::new(RefCell::new(Cell::new(1, 2)));
+        let top_right = Rc::new(RefCell::new(Cell::new(0, 2)));
+
+        (*cell).borrow_mut().top = Some(Rc::downgrade(&top));
+        (*cell).borrow_mut().right = Some(Rc::downgrade(&right));
+        (*cell).borrow_mut().top_right = Some(Rc::downgrade(&top_right));
+
+        assert!((*cell).borrow().can_link_to(Rc::downgrade(&top_right)));
+
+        (*top).borrow_mut().link_to(Rc::downgrade(&right));
+
+        assert!(!(*cell).borrow().can_link_to(Rc::downgrade(&top_right)));
+        assert!((*cell).borrow().can_link_to(Rc::downgrade(&top)));
+    }
 }
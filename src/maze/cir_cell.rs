@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type CirCellWeakLink = Weak<RefCell<CirCell>>;
+pub type CirCellStrongLink = Rc<RefCell<CirCell>>;
+
+
+/*
+ * The CirCell class used to represent a cell in the circular maze. The row is the index
+ * of the ring, counted from the centre, and the column is the index of the cell in the ring.
+ */
+pub struct CirCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<CirCellWeakLink>,
+
+    pub cw: Option<CirCellWeakLink>,
+    pub ccw: Option<CirCellWeakLink>,
+    pub inward: Option<CirCellWeakLink>,
+    // a cell may have more neighbours in the outer ring, as the rings subdivide outwards
+    pub outward: Vec<CirCellWeakLink>,
+}
+
+impl PartialEq for CirCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl CirCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            cw: None,
+            ccw: None,
+            inward: None,
+            outward: vec![],
+        }
+    }
+}
+
+
+impl MazeCell for CirCell {
+    fn link_list(&self) -> &Vec<CirCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<CirCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<CirCellWeakLink> {
+        let mut lst: Vec<CirCellWeakLink> = vec![];
+
+        if let Some(cell) = &self.cw {
+            lst.push(Weak::clone(cell));
+        }
+        if let Some(cell) = &self.ccw {
+            lst.push(Weak::clone(cell));
+        }
+        if let Some(cell) = &self.inward {
+            lst.push(Weak::clone(cell));
+        }
+        lst.extend(self.outward.iter().map(Weak::clone));
+
+        lst
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn cir_cell_neighbours() {
+        let cell1 = Rc::new(RefCell::new(CirCell::new(1, 0)));
+        let cell2 = Rc::new(RefCell::new(CirCell::new(1, 1)));
+        let cell3 = Rc::new(RefCell::new(CirCell::new(2, 0)));
+        let cell4 = Rc::new(RefCell::new(CirCell::new(2, 1)));
+
+        (*cell1).borrow_mut().cw = Some(Rc::downgrade(&cell2));
+        (*cell1).borrow_mut().outward = vec![Rc::downgrade(&cell3), Rc::downgrade(&cell4)];
+
+        assert_eq!(3, (*cell1).borrow().neighbours().len());
+    }
+
+
+    #[test]
+    fn cir_cell_link_to() {
+        let cell1 = Rc::new(RefCell::new(CirCell::new(1, 0)));
+        let cell2 = Rc::new(RefCell::new(CirCell::new(0, 0)));
+
+        (*cell1).borrow_mut().link_to(Rc::downgrade(&cell2));
+
+        assert!((*cell1).borrow().is_linked_to(Rc::downgrade(&cell2)));
+        assert!(!(*cell2).borrow().has_linked_cells());
+    }
+}
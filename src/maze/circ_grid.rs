@@ -0,0 +1,170 @@
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::cir_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a circular maze represented in polar coordinates. It consists of concentric rings
+ * of cells around a single cell in the centre. As the rings grow outwards, their cells are
+ * subdivided, so that all cells remain roughly as wide as they are high.
+ */
+pub struct CircGrid {
+    pub rings: u16,
+
+    pub cells: Vec<Vec<Option<cir_cell::CirCellStrongLink>>>,
+}
+
+impl CircGrid {
+    /*
+     * Creates a grid of the specified number of rings, including the cell in the centre.
+     * Panics if there are no rings.
+     */
+    pub fn new(rings: u16) -> Self {
+        assert!(rings > 0, "rings must be positive");
+
+        Self {
+            rings,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        let ring_height = 1.0 / self.rings as f64;
+
+        for row in 0..self.rings as usize {
+            let count = if row == 0 {
+                1
+            } else {
+                // the number of cells is a multiple of the previous ring, whose cells
+                // are split into as many parts, as the ring is longer
+                let previous = self.cells[row - 1].len();
+                let circumference = 2.0 * PI * row as f64 * ring_height;
+                let cell_width = circumference / previous as f64;
+                previous * ((cell_width / ring_height).round() as usize).max(1)
+            };
+
+            self.cells.push((0..count)
+                .map(|col| Some(Rc::new(RefCell::new(cir_cell::CirCell::new(row as u16, col as u16)))))
+                .collect());
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for row in 1..self.rings as usize {
+            let count = self.cells[row].len();
+            let ratio = count / self.cells[row - 1].len();
+
+            for col in 0..count {
+                let cell = self.cells[row][col].as_ref().unwrap();
+                let inward = self.cells[row - 1][col / ratio].as_ref().unwrap();
+
+                // a ring of a single cell would be its own neighbour
+                if count > 1 {
+                    RefCell::borrow_mut(cell).cw = Some(Rc::downgrade(
+                        self.cells[row][(col + 1) % count].as_ref().unwrap()));
+                    RefCell::borrow_mut(cell).ccw = Some(Rc::downgrade(
+                        self.cells[row][(col + count - 1) % count].as_ref().unwrap()));
+                }
+
+                RefCell::borrow_mut(cell).inward = Some(Rc::downgrade(inward));
+                RefCell::borrow_mut(inward).outward.push(Rc::downgrade(cell));
+            }
+        }
+    }
+
+
+    /*
+     * Returns the total number of cells.
+     */
+    pub fn size(&self) -> usize {
+        self.cells.iter().map(Vec::len).sum()
+    }
+}
+
+
+impl MazeGrid for CircGrid {
+    type Cell = cir_cell::CirCell;
+
+
+    fn each_cell(&self) -> Vec<cir_cell::CirCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> cir_cell::CirCellWeakLink {
+        // every cell has the same chance to be picked, regardless of the size of its ring
+        let mut index = sample::gen_index(rng, self.size());
+        for row in &self.cells {
+            if index < row.len() {
+                return Rc::downgrade(row[index].as_ref().unwrap());
+            }
+            index -= row.len();
+        }
+
+        unreachable!()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+
+    #[test]
+    fn circ_grid_rings_subdivide() {
+        let mut grid = CircGrid::new(8);
+        grid.init_grid();
+
+        assert_eq!(1, grid.cells[0].len());
+        assert_eq!(6, grid.cells[1].len());
+        assert!((2..8).all(|row| grid.cells[row].len().is_multiple_of(grid.cells[row - 1].len())));
+    }
+
+
+    #[test]
+    fn circ_grid_neighbours() {
+        let mut grid = CircGrid::new(5);
+        grid.init_grid();
+
+        let centre = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+        let cell = RefCell::borrow(grid.cells[1][5].as_ref().unwrap());
+        let cw = RefCell::borrow(&cell.cw.as_ref().unwrap().upgrade().unwrap()).col;
+
+        assert_eq!(6, centre.outward.len());
+        assert!(centre.cw.is_none() && centre.inward.is_none());
+        assert_eq!(0, cw);
+    }
+
+
+    #[test]
+    fn circ_grid_rand_cell() {
+        let mut grid = CircGrid::new(5);
+        grid.init_grid();
+
+        let cell = grid.get_rand_cell(&mut thread_rng()).upgrade().unwrap();
+
+        assert!(RefCell::borrow(&cell).row < 5);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "rings must be positive")]
+    fn circ_grid_no_rings() {
+        CircGrid::new(0);
+    }
+}
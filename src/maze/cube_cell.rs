@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type CubeCellWeakLink = Weak<RefCell<CubeCell>>;
+pub type CubeCellStrongLink = Rc<RefCell<CubeCell>>;
+
+
+/*
+ * The CubeCell class used to represent a cell on a face of the cube maze. The directions
+ * are the ones seen on the unfolded net of the cube, hence the neighbours across the edges
+ * of the face may lie in any direction on the net.
+ */
+pub struct CubeCell {
+    pub face: u8,
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<CubeCellWeakLink>,
+
+    pub north: Option<CubeCellWeakLink>,
+    pub south: Option<CubeCellWeakLink>,
+    pub east: Option<CubeCellWeakLink>,
+    pub west: Option<CubeCellWeakLink>,
+}
+
+impl PartialEq for CubeCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.face == other.face && self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl CubeCell {
+    pub fn new(face: u8, row: u16, col: u16) -> Self {
+        Self {
+            face,
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+        }
+    }
+}
+
+
+impl MazeCell for CubeCell {
+    fn link_list(&self) -> &Vec<CubeCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<CubeCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<CubeCellWeakLink> {
+        [&self.north, &self.south, &self.east, &self.west]
+            .into_iter()
+            .flatten()
+            .map(Weak::clone)
+            .collect()
+    }
+}
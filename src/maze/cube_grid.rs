@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::cube_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+type Vector = [i32; 3];
+
+
+/*
+ * A face of the cube spanning '[0, size]' along each axis, given by the corner of its first
+ * cell in units of 'size', the directions of its columns and rows, and its outer normal.
+ */
+struct Face {
+    origin: Vector,
+    u: Vector,
+    v: Vector,
+    normal: Vector,
+}
+
+
+// the left, front, right, back, top and bottom faces, unfolded into a cross, whose middle
+// band holds the first four faces, with the top face above and the bottom face below the front
+const FACES: [Face; 6] = [
+    Face { origin: [0, 1, 0], u: [0, 0, 1], v: [0, -1, 0], normal: [-1, 0, 0] },
+    Face { origin: [0, 1, 1], u: [1, 0, 0], v: [0, -1, 0], normal: [0, 0, 1] },
+    Face { origin: [1, 1, 1], u: [0, 0, -1], v: [0, -1, 0], normal: [1, 0, 0] },
+    Face { origin: [1, 1, 0], u: [-1, 0, 0], v: [0, -1, 0], normal: [0, 0, -1] },
+    Face { origin: [0, 1, 0], u: [1, 0, 0], v: [0, 0, 1], normal: [0, 1, 0] },
+    Face { origin: [0, 0, 1], u: [1, 0, 0], v: [0, 0, -1], normal: [0, -1, 0] },
+];
+
+
+/*
+ * The positions of the faces on the unfolded net of the cube, as 'row, column' in faces.
+ */
+pub const NET: [(u16, u16); 6] = [(1, 0), (1, 1), (1, 2), (1, 3), (0, 1), (2, 1)];
+
+
+/*
+ * The grid of a maze covering all six faces of a cube, each of 'size x size' cells.
+ */
+pub struct CubeGrid {
+    pub size: u16,
+
+    pub cells: Vec<Vec<Vec<Option<cube_cell::CubeCellStrongLink>>>>,
+}
+
+impl CubeGrid {
+    pub fn new(size: u16) -> Self {
+        Self {
+            size,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for face in 0..FACES.len() {
+            self.cells.push(vec![]);
+            for row in 0..self.size as usize {
+                self.cells[face].push(vec![]);
+                for col in 0..self.size as usize {
+                    self.cells[face][row].push(Some(Rc::new(RefCell::new(
+                        cube_cell::CubeCell::new(face as u8, row as u16, col as u16)))));
+                }
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for cell in self.cells.iter().flatten().flatten().flatten() {
+            let mut c = RefCell::borrow_mut(cell);
+            let face_ = c.face as usize;
+            let (row_, col_) = (c.row as i32, c.col as i32);
+            let (u, v) = (FACES[face_].u, FACES[face_].v);
+
+            c.north = self.create_neighbour(face_, row_, col_, scale(v, -1));
+            c.south = self.create_neighbour(face_, row_, col_, v);
+            c.east = self.create_neighbour(face_, row_, col_, u);
+            c.west = self.create_neighbour(face_, row_, col_, scale(u, -1));
+        }
+    }
+
+
+    // finds the cell next to the one at 'row, col' in the 'direction', crossing over
+    // to the adjacent face if needed, using the centres of the cells at doubled scale
+    fn create_neighbour(&self, face: usize, row: i32, col: i32, direction: Vector)
+                        -> Option<cube_cell::CubeCellWeakLink> {
+        let size = self.size as i32;
+        let f = &FACES[face];
+        let centre = add(add(scale(f.origin, 2 * size), scale(f.u, 2 * col + 1)),
+                         scale(f.v, 2 * row + 1));
+
+        // the next cell on the same face, or the one right behind the edge of the cube
+        let next = add(centre, scale(direction, 2));
+        let (face, next) = if position(f, size, next).is_some() {
+            (face, next)
+        } else {
+            (FACES.iter().position(|f| f.normal == direction)?,
+             add(add(centre, direction), scale(f.normal, -1)))
+        };
+
+        let (r, c) = position(&FACES[face], size, next)?;
+        Some(Rc::downgrade(self.cells[face][r as usize][c as usize].as_ref().unwrap()))
+    }
+}
+
+
+// returns the row and column of the cell centred at the point, if it lies on the face
+fn position(face: &Face, size: i32, point: Vector) -> Option<(i32, i32)> {
+    let local = add(point, scale(face.origin, -2 * size));
+    let (a, b) = (dot(local, face.u), dot(local, face.v));
+    if dot(local, face.normal) != 0 || a <= 0 || b <= 0 || a >= 2 * size || b >= 2 * size {
+        return None;
+    }
+    Some(((b - 1) / 2, (a - 1) / 2))
+}
+
+
+fn add(a: Vector, b: Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+
+fn scale(a: Vector, k: i32) -> Vector {
+    [a[0] * k, a[1] * k, a[2] * k]
+}
+
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+
+impl MazeGrid for CubeGrid {
+    type Cell = cube_cell::CubeCell;
+
+
+    fn each_cell(&self) -> Vec<cube_cell::CubeCellStrongLink> {
+        self.cells.iter().flatten().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> cube_cell::CubeCellWeakLink {
+        let face_ = sample::gen_index(rng, FACES.len());
+        let row_ = sample::gen_index(rng, self.size as usize);
+        let col_ = sample::gen_index(rng, self.size as usize);
+
+        Rc::downgrade(self.cells[face_][row_][col_].as_ref().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    // returns the face, row and column of the neighbour
+    fn at(cell: &Option<cube_cell::CubeCellWeakLink>) -> (u8, u16, u16) {
+        let cell = cell.as_ref().unwrap().upgrade().unwrap();
+        let c = RefCell::borrow(&cell);
+        (c.face, c.row, c.col)
+    }
+
+
+    #[test]
+    fn cube_grid_crosses_edges() {
+        let mut grid = CubeGrid::new(4);
+        grid.init_grid();
+
+        let front = RefCell::borrow(grid.cells[1][0][2].as_ref().unwrap());
+        let top = RefCell::borrow(grid.cells[4][1][0].as_ref().unwrap());
+        let back = RefCell::borrow(grid.cells[3][3][3].as_ref().unwrap());
+
+        assert_eq!((4, 3, 2), at(&front.north));
+        assert_eq!((1, 0, 1), at(&front.west));
+        // the left edge of the top face folds down onto the top edge of the left face
+        assert_eq!((0, 0, 1), at(&top.west));
+        // the right edge of the back face joins the left face
+        assert_eq!((0, 3, 0), at(&back.east));
+        // the bottom edge of the back face joins the far edge of the bottom face
+        assert_eq!((5, 3, 0), at(&back.south));
+    }
+
+
+    #[test]
+    fn cube_grid_neighbours_are_mutual() {
+        let mut grid = CubeGrid::new(3);
+        grid.init_grid();
+
+        for cell in grid.each_cell() {
+            let c = RefCell::borrow(&cell);
+            assert_eq!(4, c.neighbours().len());
+
+            for neighbour in c.neighbours() {
+                let n = neighbour.upgrade().unwrap();
+                let is_mutual = RefCell::borrow(&n).neighbours().iter()
+                    .any(|m| *RefCell::borrow(&m.upgrade().unwrap()) == *c);
+                assert!(is_mutual);
+            }
+        }
+    }
+}
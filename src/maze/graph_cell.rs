@@ -0,0 +1,59 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type GraphCellWeakLink = Weak<RefCell<GraphCell>>;
+pub type GraphCellStrongLink = Rc<RefCell<GraphCell>>;
+
+
+/*
+ * The GraphCell class used to represent a node of a user-supplied graph, e.g. a room
+ * of a floor plan, or a crossing of a road network.
+ */
+pub struct GraphCell {
+    pub index: u32,
+    pub position: Option<(f64, f64)>,
+
+    links: Vec<GraphCellWeakLink>,
+
+    pub adjacent: Vec<GraphCellWeakLink>,
+}
+
+impl PartialEq for GraphCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+
+impl GraphCell {
+    pub fn new(index: u32, position: Option<(f64, f64)>) -> Self {
+        Self {
+            index,
+            position,
+
+            links: vec![],
+
+            adjacent: vec![],
+        }
+    }
+}
+
+
+impl MazeCell for GraphCell {
+    fn link_list(&self) -> &Vec<GraphCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<GraphCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<GraphCellWeakLink> {
+        self.adjacent.iter().map(Weak::clone).collect()
+    }
+}
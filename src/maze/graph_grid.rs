@@ -0,0 +1,186 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::graph_cell;
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
+
+
+/*
+ * The grid of a maze over any undirected graph, given by its nodes, which may have a position
+ * in the plane, and the edges between them as pairs of node indices. The maze is a spanning
+ * tree of the graph, hence the graph shall be connected.
+ */
+pub struct GraphGrid {
+    positions: Vec<Option<(f64, f64)>>,
+    edges: Vec<(usize, usize)>,
+
+    pub cells: Vec<graph_cell::GraphCellStrongLink>,
+}
+
+impl GraphGrid {
+    /*
+     * Creates the graph of as many nodes as there are positions. Panics if there are no nodes,
+     * if an edge refers to a missing node, or if the graph is not connected, as a single maze
+     * could not reach all nodes. The loops and the repeated edges are ignored.
+     */
+    pub fn new(positions: Vec<Option<(f64, f64)>>, edges: Vec<(usize, usize)>) -> Self {
+        assert!(!positions.is_empty(), "graph has no nodes");
+        assert!(edges.iter().all(|&(a, b)| a < positions.len() && b < positions.len()),
+                "edge refers to a missing node");
+        assert!(is_connected(positions.len(), &edges), "graph is not connected");
+
+        Self {
+            positions,
+            edges,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        self.cells = self.positions.iter()
+            .enumerate()
+            .map(|(i, &position)| Rc::new(RefCell::new(graph_cell::GraphCell::new(i as u32, position))))
+            .collect();
+    }
+
+
+    fn configure_cells(&mut self) {
+        for &(a, b) in &self.edges {
+            let (cell, other) = (&self.cells[a], &self.cells[b]);
+            if a == b || RefCell::borrow(cell).adjacent.iter()
+                .any(|n| RefCell::borrow(&n.upgrade().unwrap()).index == b as u32) {
+                continue;
+            }
+
+            RefCell::borrow_mut(cell).adjacent.push(Rc::downgrade(other));
+            RefCell::borrow_mut(other).adjacent.push(Rc::downgrade(cell));
+        }
+    }
+
+
+    /*
+     * Returns the passages of the maze as pairs of node indices, the lower one first.
+     */
+    pub fn passages(&self) -> Vec<(usize, usize)> {
+        let mut passages = vec![];
+        for cell in &self.cells {
+            let c = RefCell::borrow(cell);
+            for link in c.links() {
+                let other = RefCell::borrow(&link.upgrade().unwrap()).index;
+                if c.index < other {
+                    passages.push((c.index as usize, other as usize));
+                }
+            }
+        }
+        passages.sort_unstable();
+        passages
+    }
+}
+
+
+// checks if all nodes are reachable from the first one along the edges
+fn is_connected(nodes: usize, edges: &[(usize, usize)]) -> bool {
+    let mut adjacent = vec![vec![]; nodes];
+    for &(a, b) in edges {
+        adjacent[a].push(b);
+        adjacent[b].push(a);
+    }
+
+    let mut seen = vec![false; nodes];
+    seen[0] = true;
+    let mut stack = vec![0];
+    let mut reached = 1;
+    while let Some(node) = stack.pop() {
+        for &next in &adjacent[node] {
+            if !seen[next] {
+                seen[next] = true;
+                reached += 1;
+                stack.push(next);
+            }
+        }
+    }
+
+    reached == nodes
+}
+
+
+impl MazeGrid for GraphGrid {
+    type Cell = graph_cell::GraphCell;
+
+
+    fn each_cell(&self) -> Vec<graph_cell::GraphCellStrongLink> {
+        self.cells.iter().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> graph_cell::GraphCellWeakLink {
+        Rc::downgrade(sample::choose(rng, &self.cells).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use crate::Algo;
+    use crate::algos::build_generic_maze;
+
+
+    #[test]
+    fn graph_grid_ignores_loops_and_repeated_edges() {
+        let mut grid = GraphGrid::new(vec![None; 3], vec![(0, 1), (1, 0), (1, 1), (1, 2)]);
+        grid.init_grid();
+
+        assert_eq!(1, RefCell::borrow(&grid.cells[0]).neighbours().len());
+        assert_eq!(2, RefCell::borrow(&grid.cells[1]).neighbours().len());
+    }
+
+
+    #[test]
+    #[should_panic(expected = "missing node")]
+    fn graph_grid_missing_node() {
+        GraphGrid::new(vec![None; 2], vec![(0, 2)]);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "graph has no nodes")]
+    fn graph_grid_empty() {
+        GraphGrid::new(vec![], vec![]);
+    }
+
+
+    #[test]
+    #[should_panic(expected = "graph is not connected")]
+    fn graph_grid_disconnected() {
+        // two triangles apart from each other
+        GraphGrid::new(vec![None; 6], vec![(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    }
+
+
+    #[test]
+    fn graph_grid_spanning_tree() {
+        // a wheel of 6 nodes around a hub, with all spokes and rim edges
+        let mut edges: Vec<(usize, usize)> = (1..=6).map(|i| (0, i)).collect();
+        edges.extend((1..=6).map(|i| (i, i % 6 + 1)));
+
+        for algo in [Algo::RecursiveBacktracking, Algo::Kruskal] {
+            let mut grid = GraphGrid::new(vec![None; 7], edges.clone());
+            grid.init_grid();
+
+            build_generic_maze(&algo, &grid, &mut thread_rng());
+
+            assert_eq!(6, grid.passages().len());
+        }
+    }
+}
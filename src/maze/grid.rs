@@ -1,27 +1,179 @@
 use std::cell::RefCell;
-use std::rc::{Rc, Weak};
-use rand::{thread_rng, Rng};
-use crate::maze::cell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::{cell, mask};
+use crate::maze::topology::{MazeCell, MazeGrid};
+use crate::rng::sample;
+
+
+/*
+ * The ways the edges of the grid join each other.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    // the edges are the outer walls of the maze
+    Box,
+    // the right edge joins the left
+    Cylinder,
+    // the right edge joins the left, and the bottom edge joins the top
+    Torus,
+    // the right edge joins the left upside down
+    Mobius,
+    // the right edge joins the left upside down, and the bottom edge joins the top
+    Klein,
+}
+
+impl Boundary {
+    /*
+     * Checks if the right edge joins the left.
+     */
+    pub fn wraps_cols(&self) -> bool {
+        *self != Boundary::Box
+    }
+
+
+    /*
+     * Checks if the bottom edge joins the top.
+     */
+    pub fn wraps_rows(&self) -> bool {
+        matches!(self, Boundary::Torus | Boundary::Klein)
+    }
+
+
+    /*
+     * Checks if the rows are flipped upside down, when crossing from the right edge to the left.
+     */
+    pub fn flips_rows(&self) -> bool {
+        matches!(self, Boundary::Mobius | Boundary::Klein)
+    }
+}
 
 
 pub struct Grid {
     pub width: u16,
     pub height: u16,
 
+    pub boundary: Boundary,
+
+    // when set, the cells are also adjacent to their diagonal neighbours
+    pub diagonal: bool,
+
+    // when set, the cells switched off in the mask are left out of the grid
+    pub mask: Option<mask::Mask>,
+
     pub cells: Vec<Vec<Option<cell::CellStrongLink>>>,
 }
 
+
+/*
+ * Creates a deep copy of the grid, with its own cells linked the same way as the original ones.
+ */
+impl Clone for Grid {
+    fn clone(&self) -> Self {
+        let mut grid = Self {
+            mask: self.mask.clone(),
+            cells: vec![],
+            ..*self
+        };
+        grid.init_grid();
+
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let (Some(cell), Some(copy)) = (cell, &grid.cells[row][col]) else {
+                    continue;
+                };
+
+                let position = |link: &cell::CellWeakLink| {
+                    let l = link.upgrade().unwrap();
+                    let l = RefCell::borrow(&l);
+                    (l.row as usize, l.col as usize)
+                };
+
+                for link in RefCell::borrow(cell).links() {
+                    let (r, c) = position(&link);
+                    RefCell::borrow_mut(copy).link_to(
+                        Rc::downgrade(grid.cells[r][c].as_ref().unwrap()));
+                }
+                if let Some((r, c)) = RefCell::borrow(cell).portal.as_ref().map(position) {
+                    RefCell::borrow_mut(copy).portal = Some(Rc::downgrade(grid.cells[r][c].as_ref().unwrap()));
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+
 impl Grid {
     pub fn new(width: u16, height:u16) -> Self {
         Self {
             width,
             height,
 
+            boundary: Boundary::Box,
+            diagonal: false,
+
+            mask: None,
+
             cells: vec![],
         }
     }
 
 
+    /*
+     * Creates a toroidal grid, whose border cells are adjacent to the cells on the opposite
+     * side.
+     */
+    pub fn new_toroidal(width: u16, height: u16) -> Self {
+        Self::with_boundary(width, height, Boundary::Torus)
+    }
+
+
+    /*
+     * Creates a grid, whose edges join each other as set by the 'boundary'. Wrapping is only
+     * applied along axes at least 3 cells long, as shorter ones would make two cells adjacent
+     * through more than one wall.
+     */
+    pub fn with_boundary(width: u16, height: u16, boundary: Boundary) -> Self {
+        Self {
+            boundary,
+            ..Self::new(width, height)
+        }
+    }
+
+
+    /*
+     * Creates a zeta grid, whose cells are adjacent to all eight surrounding cells, so the
+     * passages may also lead diagonally, though two diagonal passages never cross.
+     */
+    pub fn new_zeta(width: u16, height: u16) -> Self {
+        Self {
+            diagonal: true,
+            ..Self::new(width, height)
+        }
+    }
+
+
+    /*
+     * Creates a grid shaped by the mask, which has no cells where the mask is switched off.
+     * Panics if the mask has no cells at all, or if its parts do not touch each other, as
+     * a single maze could not cover them all.
+     */
+    pub fn with_mask(mask: mask::Mask) -> Self {
+        assert!(mask.count() > 0, "mask has no cells");
+        assert!(mask.is_connected(), "mask is not connected");
+
+        Self {
+            width: mask.width,
+            height: mask.height,
+
+            mask: Some(mask),
+            ..Self::new(0, 0)
+        }
+    }
+
+
     pub fn init_grid(&mut self) {
         self.prepare_grid();
         self.configure_cells();
@@ -32,6 +184,10 @@ impl Grid {
         for row in 0..self.height as usize {
             self.cells.push(vec![]);
             for col in 0..self.width as usize{
+                if self.mask.as_ref().is_some_and(|mask| !mask.is_on(row, col)) {
+                    self.cells[row].push(None);
+                    continue;
+                }
                 self.cells[row].push(Some(Rc::new(RefCell::new(
                     cell::Cell::new(row as u16, col as u16)))));
             }
@@ -41,7 +197,7 @@ impl Grid {
 
     fn configure_cells(&mut self) {
         for row in &self.cells {
-            for cell in row.iter() {
+            for cell in row.iter().filter(|cell| cell.is_some()) {
                 let row_ = RefCell::borrow(cell.as_ref().unwrap()).row as i32;
                 let col_ = RefCell::borrow(cell.as_ref().unwrap()).col as i32;
 
@@ -50,25 +206,81 @@ impl Grid {
                 RefCell::borrow_mut(cell.as_ref().unwrap()).left = self.create_neighbour(row_, col_ - 1);
                 RefCell::borrow_mut(cell.as_ref().unwrap()).right = self.create_neighbour(row_, col_ + 1);
 
+                if self.diagonal {
+                    let mut c = RefCell::borrow_mut(cell.as_ref().unwrap());
+                    c.top_left = self.create_neighbour(row_ - 1, col_ - 1);
+                    c.top_right = self.create_neighbour(row_ - 1, col_ + 1);
+                    c.bottom_left = self.create_neighbour(row_ + 1, col_ - 1);
+                    c.bottom_right = self.create_neighbour(row_ + 1, col_ + 1);
+                }
+
             }
         }
     }
 
 
     fn create_neighbour(&self, row: i32, col: i32) -> Option<cell::CellWeakLink> {
-        if 0 <= row && row <= (self.width - 1) as i32 && 0 <= col && col <= (self.height - 1) as i32 {
-            return Some(Rc::downgrade(&self.cells[row as usize][col as usize].as_ref().unwrap()));
+        let (height, width) = (self.height as i32, self.width as i32);
+
+        let (mut row, mut col) = (row, col);
+        if self.wraps_cols() && !(0..width).contains(&col) {
+            col = col.rem_euclid(width);
+            if self.boundary.flips_rows() {
+                row = height - 1 - row;
+            }
+        }
+        if self.wraps_rows() {
+            row = row.rem_euclid(height);
+        }
+
+        if 0 <= row && row < height && 0 <= col && col < width {
+            return self.cells[row as usize][col as usize].as_ref().map(Rc::downgrade);
         };
         None
     }
 
 
-    pub fn get_rand_cell(&self) -> cell::CellWeakLink {
-        let mut rng = thread_rng();
-        let row_ = rng.gen_range(0..self.height) as usize;
-        let col_ = rng.gen_range(0..self.width) as usize;
+    /*
+     * Checks if the right edge of the grid actually joins the left, as set by the boundary.
+     */
+    pub fn wraps_cols(&self) -> bool {
+        self.boundary.wraps_cols() && self.width >= 3
+    }
 
-        Rc::downgrade(&self.cells[row_][col_].as_ref().unwrap())
+
+    /*
+     * Checks if the bottom edge of the grid actually joins the top, as set by the boundary.
+     */
+    pub fn wraps_rows(&self) -> bool {
+        self.boundary.wraps_rows() && self.height >= 3
+    }
+
+
+    pub fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> cell::CellWeakLink {
+        // draws again when hitting a cell left out by the mask, so all cells are equally likely
+        loop {
+            let row_ = sample::gen_index(rng, self.height as usize);
+            let col_ = sample::gen_index(rng, self.width as usize);
+
+            if let Some(cell) = &self.cells[row_][col_] {
+                return Rc::downgrade(cell);
+            }
+        }
+    }
+}
+
+
+impl MazeGrid for Grid {
+    type Cell = cell::Cell;
+
+
+    fn each_cell(&self) -> Vec<cell::CellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> cell::CellWeakLink {
+        Grid::get_rand_cell(self, rng)
     }
 }
 
@@ -76,6 +288,7 @@ impl Grid {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::thread_rng;
 
 
     #[test]
@@ -87,6 +300,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn grid_init() {
         let mut grid = Grid::new(5, 5);
         grid.init_grid();
@@ -100,6 +314,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn grid_top_border_none() {
         let mut grid = Grid::new(5, 5);
         grid.init_grid();
@@ -113,6 +328,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn grid_right_border_none() {
         let mut grid = Grid::new(5, 5);
         grid.init_grid();
@@ -126,6 +342,7 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::bool_assert_comparison, clippy::needless_borrow)]
     fn grid_inner_cells_some() {
         let mut grid = Grid::new(5, 5);
         grid.init_grid();
@@ -139,14 +356,162 @@ mod tests {
 
 
     #[test]
+    #[allow(clippy::needless_borrow)]
     fn grid_rand_cell() {
         let mut grid = Grid::new(5, 5);
         grid.init_grid();
 
-        let rand_cell = &grid.get_rand_cell();
+        let rand_cell = &grid.get_rand_cell(&mut thread_rng());
         let row = (&*rand_cell.upgrade().unwrap()).borrow().row;
 
         assert!(row < grid.height);
     }
 
-}
\ No newline at end of file
+
+    #[test]
+    fn grid_non_square_borders() {
+        let mut grid = Grid::new(7, 3);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[2][6].as_ref().unwrap());
+
+        assert!(cell.bottom.is_none() && cell.right.is_none());
+        assert!(cell.top.is_some() && cell.left.is_some());
+    }
+
+
+    #[test]
+    fn grid_toroidal_wraps_borders() {
+        let mut grid = Grid::new_toroidal(5, 4);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+        let top = RefCell::borrow(&cell.top.as_ref().unwrap().upgrade().unwrap()).row;
+        let left = RefCell::borrow(&cell.left.as_ref().unwrap().upgrade().unwrap()).col;
+
+        assert_eq!(3, top);
+        assert_eq!(4, left);
+    }
+
+
+    #[test]
+    fn grid_toroidal_short_axis_not_wrapped() {
+        let mut grid = Grid::new_toroidal(5, 2);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+
+        assert!(cell.top.is_none());
+        assert!(cell.left.is_some());
+    }
+
+
+    #[test]
+    fn grid_cylinder_wraps_cols_only() {
+        let mut grid = Grid::with_boundary(5, 4, Boundary::Cylinder);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[0][4].as_ref().unwrap());
+        let right = RefCell::borrow(&cell.right.as_ref().unwrap().upgrade().unwrap()).col;
+
+        assert_eq!(0, right);
+        assert!(cell.top.is_none());
+    }
+
+
+    #[test]
+    fn grid_mobius_flips_rows() {
+        let mut grid = Grid::with_boundary(5, 4, Boundary::Mobius);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[0][4].as_ref().unwrap());
+        let right = cell.right.as_ref().unwrap().upgrade().unwrap();
+        let back = RefCell::borrow(&right).left.as_ref().unwrap().upgrade().unwrap();
+
+        assert_eq!((3, 0), (RefCell::borrow(&right).row, RefCell::borrow(&right).col));
+        assert!(*RefCell::borrow(&back) == *cell);
+        assert!(RefCell::borrow(grid.cells[3][2].as_ref().unwrap()).bottom.is_none());
+    }
+
+
+    #[test]
+    fn grid_klein_wraps_both() {
+        let mut grid = Grid::with_boundary(5, 4, Boundary::Klein);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[1][0].as_ref().unwrap());
+        let left = cell.left.as_ref().unwrap().upgrade().unwrap();
+        let top = cell.top.as_ref().unwrap().upgrade().unwrap();
+
+        assert_eq!((2, 4), (RefCell::borrow(&left).row, RefCell::borrow(&left).col));
+        assert_eq!((0, 0), (RefCell::borrow(&top).row, RefCell::borrow(&top).col));
+        assert_eq!(3, RefCell::borrow(&RefCell::borrow(grid.cells[0][0].as_ref().unwrap())
+            .top.as_ref().unwrap().upgrade().unwrap()).row);
+    }
+
+
+    #[test]
+    fn grid_zeta_diagonals() {
+        let mut grid = Grid::new_zeta(4, 3);
+        grid.init_grid();
+
+        let corner = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+        let inner = RefCell::borrow(grid.cells[1][1].as_ref().unwrap());
+
+        assert_eq!(3, corner.neighbours().len());
+        assert_eq!(8, inner.neighbours().len());
+        assert!(corner.top_left.is_none() && corner.bottom_right.is_some());
+    }
+
+
+    #[test]
+    fn grid_masked() {
+        let mut grid = Grid::with_mask(mask::Mask::parse("X..\n.X.\n..."));
+        grid.init_grid();
+
+        assert_eq!((3, 3), (grid.width, grid.height));
+        assert!(grid.cells[0][0].is_none() && grid.cells[1][1].is_none());
+
+        // the cells next to the missing ones have no neighbour on that side
+        let cell = RefCell::borrow(grid.cells[1][0].as_ref().unwrap());
+        assert!(cell.top.is_none() && cell.right.is_none() && cell.bottom.is_some());
+
+        let mut rng = thread_rng();
+        for _ in 0..50 {
+            let rand_cell = grid.get_rand_cell(&mut rng).upgrade().unwrap();
+            let (row, col) = (RefCell::borrow(&rand_cell).row, RefCell::borrow(&rand_cell).col);
+            assert!((row, col) != (0, 0) && (row, col) != (1, 1));
+        }
+    }
+
+
+    #[test]
+    #[should_panic(expected = "mask has no cells")]
+    fn grid_masked_empty() {
+        Grid::with_mask(mask::Mask::parse("XX\nXX"));
+    }
+
+
+    #[test]
+    #[should_panic(expected = "mask is not connected")]
+    fn grid_masked_disconnected() {
+        Grid::with_mask(mask::Mask::parse("...XX...\n...XX...\nXXXXXXXX\n...XX..."));
+    }
+
+
+    #[test]
+    fn grid_clone_copies_links() {
+        let mut grid = Grid::new(3, 3);
+        grid.init_grid();
+        let (a, b) = (grid.cells[1][1].as_ref().unwrap(), grid.cells[1][2].as_ref().unwrap());
+        RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+        RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+
+        let copy = grid.clone();
+        RefCell::borrow_mut(a).unlink_from(Rc::downgrade(b));
+
+        let (a, b) = (copy.cells[1][1].as_ref().unwrap(), copy.cells[1][2].as_ref().unwrap());
+        assert!(RefCell::borrow(a).is_linked_to(Rc::downgrade(b)));
+        assert!(RefCell::borrow(b).is_linked_to(Rc::downgrade(a)));
+    }
+}
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type HexCellWeakLink = Weak<RefCell<HexCell>>;
+pub type HexCellStrongLink = Rc<RefCell<HexCell>>;
+
+
+/*
+ * The HexCell class used to represent a flat-topped hexagonal cell in the hex maze.
+ */
+pub struct HexCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<HexCellWeakLink>,
+
+    pub north: Option<HexCellWeakLink>,
+    pub south: Option<HexCellWeakLink>,
+    pub northeast: Option<HexCellWeakLink>,
+    pub northwest: Option<HexCellWeakLink>,
+    pub southeast: Option<HexCellWeakLink>,
+    pub southwest: Option<HexCellWeakLink>,
+}
+
+impl PartialEq for HexCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl HexCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            northeast: None,
+            northwest: None,
+            southeast: None,
+            southwest: None,
+        }
+    }
+}
+
+
+impl MazeCell for HexCell {
+    fn link_list(&self) -> &Vec<HexCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<HexCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<HexCellWeakLink> {
+        [&self.north, &self.south, &self.northeast, &self.northwest, &self.southeast, &self.southwest]
+            .into_iter()
+            .flatten()
+            .map(Weak::clone)
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn hex_cell_neighbours() {
+        let cell1 = Rc::new(RefCell::new(HexCell::new(1, 1)));
+        let cell2 = Rc::new(RefCell::new(HexCell::new(0, 1)));
+        let cell3 = Rc::new(RefCell::new(HexCell::new(1, 2)));
+
+        (*cell1).borrow_mut().north = Some(Rc::downgrade(&cell2));
+        (*cell1).borrow_mut().northeast = Some(Rc::downgrade(&cell3));
+
+        assert_eq!(2, (*cell1).borrow().neighbours().len());
+    }
+}
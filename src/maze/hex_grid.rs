@@ -0,0 +1,142 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::hex_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a hex maze. The flat-topped hexagonal cells are laid out in offset columns,
+ * where every odd column is shifted down by half a cell.
+ */
+pub struct HexGrid {
+    pub width: u16,
+    pub height: u16,
+
+    pub cells: Vec<Vec<Option<hex_cell::HexCellStrongLink>>>,
+}
+
+impl HexGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for row in 0..self.height as usize {
+            self.cells.push(vec![]);
+            for col in 0..self.width as usize {
+                self.cells[row].push(Some(Rc::new(RefCell::new(
+                    hex_cell::HexCell::new(row as u16, col as u16)))));
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for row in &self.cells {
+            for cell in row.iter() {
+                let cell = cell.as_ref().unwrap();
+                let row_ = RefCell::borrow(cell).row as i32;
+                let col_ = RefCell::borrow(cell).col as i32;
+
+                // the diagonal neighbours of the shifted columns lie one row lower
+                let (north_diagonal, south_diagonal) = if col_ % 2 == 0 {
+                    (row_ - 1, row_)
+                } else {
+                    (row_, row_ + 1)
+                };
+
+                let mut c = RefCell::borrow_mut(cell);
+                c.north = self.create_neighbour(row_ - 1, col_);
+                c.south = self.create_neighbour(row_ + 1, col_);
+                c.northeast = self.create_neighbour(north_diagonal, col_ + 1);
+                c.northwest = self.create_neighbour(north_diagonal, col_ - 1);
+                c.southeast = self.create_neighbour(south_diagonal, col_ + 1);
+                c.southwest = self.create_neighbour(south_diagonal, col_ - 1);
+            }
+        }
+    }
+
+
+    fn create_neighbour(&self, row: i32, col: i32) -> Option<hex_cell::HexCellWeakLink> {
+        if 0 <= row && row < self.height as i32 && 0 <= col && col < self.width as i32 {
+            return Some(Rc::downgrade(self.cells[row as usize][col as usize].as_ref().unwrap()));
+        };
+        None
+    }
+}
+
+
+impl MazeGrid for HexGrid {
+    type Cell = hex_cell::HexCell;
+
+
+    fn each_cell(&self) -> Vec<hex_cell::HexCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> hex_cell::HexCellWeakLink {
+        let row_ = sample::gen_index(rng, self.height as usize);
+        let col_ = sample::gen_index(rng, self.width as usize);
+
+        Rc::downgrade(self.cells[row_][col_].as_ref().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn hex_grid_inner_cell_neighbours() {
+        let mut grid = HexGrid::new(5, 5);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[2][2].as_ref().unwrap());
+
+        assert_eq!(6, cell.neighbours().len());
+    }
+
+
+    #[test]
+    fn hex_grid_odd_column_diagonals() {
+        let mut grid = HexGrid::new(5, 5);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[2][1].as_ref().unwrap());
+        let northeast = RefCell::borrow(&cell.northeast.as_ref().unwrap().upgrade().unwrap()).row;
+        let southwest = RefCell::borrow(&cell.southwest.as_ref().unwrap().upgrade().unwrap()).row;
+
+        assert_eq!(2, northeast);
+        assert_eq!(3, southwest);
+    }
+
+
+    #[test]
+    fn hex_grid_corner_cell_neighbours() {
+        let mut grid = HexGrid::new(5, 5);
+        grid.init_grid();
+
+        let cell = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+
+        // only the south and the southeast neighbours exist
+        assert_eq!(2, cell.neighbours().len());
+    }
+}
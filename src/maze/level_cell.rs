@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type LevelCellWeakLink = Weak<RefCell<LevelCell>>;
+pub type LevelCellStrongLink = Rc<RefCell<LevelCell>>;
+
+
+/*
+ * The LevelCell class used to represent a cell in the multi-level maze. Besides the four
+ * neighbours on its own level, it is adjacent to the cells right above and below it.
+ */
+pub struct LevelCell {
+    pub level: u16,
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<LevelCellWeakLink>,
+
+    pub north: Option<LevelCellWeakLink>,
+    pub south: Option<LevelCellWeakLink>,
+    pub east: Option<LevelCellWeakLink>,
+    pub west: Option<LevelCellWeakLink>,
+
+    pub up: Option<LevelCellWeakLink>,
+    pub down: Option<LevelCellWeakLink>,
+}
+
+impl PartialEq for LevelCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.level == other.level && self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl LevelCell {
+    pub fn new(level: u16, row: u16, col: u16) -> Self {
+        Self {
+            level,
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+
+            up: None,
+            down: None,
+        }
+    }
+}
+
+
+impl MazeCell for LevelCell {
+    fn link_list(&self) -> &Vec<LevelCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<LevelCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<LevelCellWeakLink> {
+        [&self.north, &self.south, &self.east, &self.west, &self.up, &self.down]
+            .into_iter()
+            .flatten()
+            .map(Weak::clone)
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn level_cell_eq_includes_level() {
+        assert!(LevelCell::new(0, 2, 3) != LevelCell::new(1, 2, 3));
+        assert!(LevelCell::new(1, 2, 3) == LevelCell::new(1, 2, 3));
+    }
+}
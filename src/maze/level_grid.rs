@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::level_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a multi-level maze, a stack of 'levels' floors of 'width x height' cells,
+ * whose cells are joined to the ones right above and below them by stairs.
+ */
+pub struct LevelGrid {
+    pub levels: u16,
+    pub width: u16,
+    pub height: u16,
+
+    pub cells: Vec<Vec<Vec<Option<level_cell::LevelCellStrongLink>>>>,
+}
+
+impl LevelGrid {
+    pub fn new(levels: u16, width: u16, height: u16) -> Self {
+        Self {
+            levels,
+            width,
+            height,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for level in 0..self.levels as usize {
+            self.cells.push(vec![]);
+            for row in 0..self.height as usize {
+                self.cells[level].push(vec![]);
+                for col in 0..self.width as usize {
+                    self.cells[level][row].push(Some(Rc::new(RefCell::new(
+                        level_cell::LevelCell::new(level as u16, row as u16, col as u16)))));
+                }
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for cell in self.cells.iter().flatten().flatten().flatten() {
+            let mut c = RefCell::borrow_mut(cell);
+            let level_ = c.level as i32;
+            let row_ = c.row as i32;
+            let col_ = c.col as i32;
+
+            c.north = self.create_neighbour(level_, row_ - 1, col_);
+            c.south = self.create_neighbour(level_, row_ + 1, col_);
+            c.east = self.create_neighbour(level_, row_, col_ + 1);
+            c.west = self.create_neighbour(level_, row_, col_ - 1);
+
+            c.up = self.create_neighbour(level_ + 1, row_, col_);
+            c.down = self.create_neighbour(level_ - 1, row_, col_);
+        }
+    }
+
+
+    fn create_neighbour(&self, level: i32, row: i32, col: i32) -> Option<level_cell::LevelCellWeakLink> {
+        if 0 <= level && level < self.levels as i32
+            && 0 <= row && row < self.height as i32 && 0 <= col && col < self.width as i32 {
+            return Some(Rc::downgrade(
+                self.cells[level as usize][row as usize][col as usize].as_ref().unwrap()));
+        };
+        None
+    }
+}
+
+
+impl MazeGrid for LevelGrid {
+    type Cell = level_cell::LevelCell;
+
+
+    fn each_cell(&self) -> Vec<level_cell::LevelCellStrongLink> {
+        self.cells.iter().flatten().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> level_cell::LevelCellWeakLink {
+        let level_ = sample::gen_index(rng, self.levels as usize);
+        let row_ = sample::gen_index(rng, self.height as usize);
+        let col_ = sample::gen_index(rng, self.width as usize);
+
+        Rc::downgrade(self.cells[level_][row_][col_].as_ref().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn level_grid_neighbours() {
+        let mut grid = LevelGrid::new(3, 4, 4);
+        grid.init_grid();
+
+        let middle = RefCell::borrow(grid.cells[1][1][1].as_ref().unwrap());
+        let bottom = RefCell::borrow(grid.cells[0][0][0].as_ref().unwrap());
+        let up = middle.up.as_ref().unwrap().upgrade().unwrap();
+
+        assert_eq!(6, middle.neighbours().len());
+        assert_eq!(3, bottom.neighbours().len());
+        assert_eq!((2, 1, 1), (RefCell::borrow(&up).level, RefCell::borrow(&up).row,
+                               RefCell::borrow(&up).col));
+    }
+
+
+    #[test]
+    fn level_grid_generators_use_stairs() {
+        for build in [crate::algos::recursive_backtracking::build_maze::<LevelGrid, _>,
+                      crate::algos::kruskal::build_maze::<LevelGrid, _>] {
+            let mut grid = LevelGrid::new(3, 6, 6);
+            grid.init_grid();
+
+            build(&grid, &mut rand::thread_rng());
+
+            let links: usize = grid.each_cell().iter().map(|c| RefCell::borrow(c).links().len()).sum();
+            let stairs = grid.each_cell().iter()
+                .filter(|c| RefCell::borrow(c).links().iter().any(|l| {
+                    RefCell::borrow(&l.upgrade().unwrap()).level != RefCell::borrow(c).level
+                }))
+                .count();
+
+            assert_eq!(2 * (3 * 6 * 6 - 1), links);
+            assert!(stairs > 0);
+        }
+    }
+}
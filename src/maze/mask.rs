@@ -0,0 +1,570 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::Path;
+use image::{imageops::FilterType, DynamicImage, ImageResult};
+use crate::maze::font;
+use crate::maze::shape::Shape;
+
+
+// the pixels darker than this, or more transparent, are left out of the mask made of an image
+pub const LUMA_THRESHOLD: u8 = 128;
+pub const ALPHA_THRESHOLD: u8 = 128;
+
+
+/*
+ * The shape of a maze, stating which cells of the rectangular grid exist. It lets the mazes
+ * take the shape of letters or logos, as the grid leaves out the cells switched off.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    pub width: u16,
+    pub height: u16,
+
+    bits: Vec<Vec<bool>>,
+}
+
+
+impl Mask {
+    /*
+     * Creates a mask of the specified size with all cells switched on.
+     */
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+
+            bits: vec![vec![true; width as usize]; height as usize],
+        }
+    }
+
+
+    /*
+     * Creates a mask from its text, where each line is a row of the grid, and each character
+     * a cell. An 'X' means there is no cell, while any other character, e.g. '.', is a cell.
+     * The mask is as wide as its longest line, and there are no cells past the end of
+     * the shorter lines. Empty lines at the end are ignored. The cells are kept exactly
+     * as written, hence the parts of the mask, which do not touch, have to be joined
+     * by 'connect' before the mask shapes a grid.
+     */
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text.trim_end().lines().map(str::trim_end).collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+        let bits: Vec<Vec<bool>> = lines.iter().map(|line| {
+            let mut row: Vec<bool> = line.chars().map(|c| c != 'X').collect();
+            row.resize(width, false);
+            row
+        }).collect();
+
+        Self {
+            width: u16::try_from(width).expect("mask is too wide"),
+            height: u16::try_from(bits.len()).expect("mask is too high"),
+
+            bits,
+        }
+    }
+
+
+    /*
+     * Loads the mask from a text file, see 'parse' for its format.
+     */
+    pub fn from_txt<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+
+    /*
+     * Creates a mask from the silhouette in the image, which is scaled to the specified number
+     * of cells first. The black pixels, and the pixels more transparent than 'ALPHA_THRESHOLD',
+     * are left out, so the mask covers the light and opaque parts of the image. Only the largest
+     * part of the silhouette is kept, as scaling it down may leave specks, e.g. of anti-aliased
+     * edges, apart from it. Use 'from_image_bridged' to keep all parts.
+     */
+    pub fn from_image(img: &DynamicImage, width: u16, height: u16) -> Self {
+        let mut mask = Self::silhouette(img, width, height);
+        mask.keep_largest();
+        mask
+    }
+
+
+    /*
+     * Creates a mask from the silhouette in the image, see 'from_image', which keeps all parts
+     * of the silhouette, e.g. the separate letters of a logo, joined by bridging cells.
+     */
+    pub fn from_image_bridged(img: &DynamicImage, width: u16, height: u16) -> Self {
+        let mut mask = Self::silhouette(img, width, height);
+        mask.connect();
+        mask
+    }
+
+
+    // scales the image to the specified number of cells, and switches on the cells of its light
+    // and opaque pixels
+    fn silhouette(img: &DynamicImage, width: u16, height: u16) -> Self {
+        let (w, h) = (width as u32, height as u32);
+        let scaled = if img.width() == w && img.height() == h {
+            img.to_luma_alpha8()
+        } else {
+            // averages the pixels covered by each cell, so the edges of the silhouette are kept
+            img.resize_exact(w, h, FilterType::Triangle).to_luma_alpha8()
+        };
+
+        let mut mask = Self::new(width, height);
+        for (x, y, pixel) in scaled.enumerate_pixels() {
+            let [luma, alpha] = pixel.0;
+            mask.set(y as usize, x as usize, luma >= LUMA_THRESHOLD && alpha >= ALPHA_THRESHOLD);
+        }
+
+        mask
+    }
+
+
+    /*
+     * Loads the mask from an image file of any format supported by the 'image' crate, see
+     * 'from_image' for how the image is turned into the mask.
+     */
+    pub fn from_img<P: AsRef<Path>>(path: P, width: u16, height: u16) -> ImageResult<Self> {
+        Ok(Self::from_image(&image::open(path)?, width, height))
+    }
+
+
+    /*
+     * Creates a mask of the specified size, which has the cells whose centres lie inside
+     * the shape. The thin or sharp parts of the shape, e.g. a narrow ring or the tips of
+     * a star, may leave cells touching only by their corners, or none at all, hence all
+     * parts are joined by bridging cells.
+     */
+    pub fn from_shape(shape: &Shape, width: u16, height: u16) -> Self {
+        let mut mask = Self::new(width, height);
+        for row in 0..height as usize {
+            for col in 0..width as usize {
+                mask.set(row, col, shape.contains((col as f64 + 0.5, row as f64 + 0.5)));
+            }
+        }
+
+        mask.connect();
+        mask
+    }
+
+
+    /*
+     * Creates a mask filling the text written in the built-in bitmap font, whose pixels are
+     * squares of 'scale' x 'scale' cells. The text may span several lines. As the letters, and
+     * some of their strokes, do not touch each other, they are joined by bridging cells, so
+     * a single maze covers them all.
+     */
+    pub fn from_text(text: &str, scale: u16) -> Self {
+        let mut mask = Self::rasterise(text, scale, 0);
+        mask.connect();
+        mask
+    }
+
+
+    /*
+     * Creates a mask, whose cells surround the text written in the built-in bitmap font, so
+     * the letters are spelt by the holes in the maze. The holes inside the letters, e.g.
+     * in 'A' or 'O', are bridged to the rest of the maze through the letters.
+     */
+    pub fn from_text_negative(text: &str, scale: u16) -> Self {
+        // leaves a margin of one pixel of the font around the text
+        let mut mask = Self::rasterise(text, scale, 1);
+        for on in mask.bits.iter_mut().flatten() {
+            *on = !*on;
+        }
+        mask.connect();
+        mask
+    }
+
+
+    // draws the text into a mask with the specified margin in pixels of the font
+    fn rasterise(text: &str, scale: u16, margin: usize) -> Self {
+        assert!(scale > 0, "scale must be positive");
+        let scale = scale as usize;
+
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let advance = (font::GLYPH_WIDTH + font::SPACING, font::GLYPH_HEIGHT + font::SPACING);
+
+        // the spacing is left out after the last character and the last line
+        let pixels = |count: usize, step: usize| (count * step).saturating_sub(font::SPACING) + 2 * margin;
+        let (width, height) = (pixels(columns, advance.0) * scale, pixels(lines.len(), advance.1) * scale);
+
+        let mut mask = Self {
+            width: u16::try_from(width).expect("text is too wide"),
+            height: u16::try_from(height).expect("text is too high"),
+
+            bits: vec![vec![false; width]; height],
+        };
+
+        for (line, chars) in lines.iter().enumerate() {
+            for (index, &c) in chars.iter().enumerate() {
+                let glyph = font::glyph(c);
+
+                for row in 0..font::GLYPH_HEIGHT {
+                    for col in (0..font::GLYPH_WIDTH).filter(|&col| font::is_set(&glyph, row, col)) {
+                        let top = (margin + line * advance.1 + row) * scale;
+                        let left = (margin + index * advance.0 + col) * scale;
+                        for bits in &mut mask.bits[top..top + scale] {
+                            bits[left..left + scale].fill(true);
+                        }
+                    }
+                }
+            }
+        }
+
+        mask
+    }
+
+
+    /*
+     * Joins all parts of the mask into one, so that a single maze covers them all. All parts
+     * grow at once through the cells switched off, and where two of them meet, they may be
+     * bridged by switching on the cells they grew through. The shortest bridges are taken
+     * first, unless both parts are already joined.
+     */
+    pub fn connect(&mut self) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let (mut owner, sizes) = self.parts();
+        if sizes.len() <= 1 {
+            return;
+        }
+
+        // records the part reaching each cell first, through which cell, and how far from it
+        let mut previous = vec![vec![None; width]; height];
+        let mut distance = vec![vec![0; width]; height];
+        let mut queue: VecDeque<(usize, usize)> = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .filter(|&(r, c)| self.bits[r][c])
+            .collect();
+        let mut bridges = vec![];
+        while let Some(cell) = queue.pop_front() {
+            for (r, c) in self.adjacent(cell) {
+                match owner[r][c] {
+                    None => {
+                        owner[r][c] = owner[cell.0][cell.1];
+                        previous[r][c] = Some(cell);
+                        distance[r][c] = distance[cell.0][cell.1] + 1;
+                        queue.push_back((r, c));
+                    },
+                    Some(part) if Some(part) != owner[cell.0][cell.1] =>
+                        bridges.push((distance[cell.0][cell.1] + distance[r][c], cell, (r, c))),
+                    _ => {},
+                }
+            }
+        }
+        bridges.sort_by_key(|&(length, _, _)| length);
+
+        let mut sets: Vec<usize> = (0..sizes.len()).collect();
+        fn find(sets: &mut [usize], mut i: usize) -> usize {
+            while sets[i] != i {
+                sets[i] = sets[sets[i]];
+                i = sets[i];
+            }
+            i
+        }
+
+        for (_, a, b) in bridges {
+            let x = find(&mut sets, owner[a.0][a.1].unwrap());
+            let y = find(&mut sets, owner[b.0][b.1].unwrap());
+            if x == y {
+                continue;
+            }
+            sets[x] = y;
+
+            // switches on the cells leading back from both ends to their parts
+            for mut cell in [a, b] {
+                while !self.bits[cell.0][cell.1] {
+                    self.bits[cell.0][cell.1] = true;
+                    cell = previous[cell.0][cell.1].unwrap();
+                }
+            }
+        }
+    }
+
+
+    // switches off all parts of the mask but the largest one, or the first of the largest ones
+    fn keep_largest(&mut self) {
+        let (parts, sizes) = self.parts();
+        let Some(largest) = (0..sizes.len()).rev().max_by_key(|&part| sizes[part]) else {
+            return;
+        };
+
+        for (bits, parts) in self.bits.iter_mut().zip(parts) {
+            for (on, part) in bits.iter_mut().zip(parts) {
+                *on = part == Some(largest);
+            }
+        }
+    }
+
+
+    // labels the parts of the mask, i.e. the groups of cells reachable from each other through
+    // adjacent cells, in a single pass, and returns the part of each cell, and the size of
+    // each part
+    fn parts(&self) -> (Vec<Vec<Option<usize>>>, Vec<usize>) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut parts = vec![vec![None; width]; height];
+        let mut sizes = vec![];
+
+        for (row, col) in (0..height).flat_map(|r| (0..width).map(move |c| (r, c))) {
+            if !self.bits[row][col] || parts[row][col].is_some() {
+                continue;
+            }
+
+            let part = sizes.len();
+            parts[row][col] = Some(part);
+            let mut stack = vec![(row, col)];
+            let mut size = 0;
+            while let Some(cell) = stack.pop() {
+                size += 1;
+                for (r, c) in self.adjacent(cell) {
+                    if self.bits[r][c] && parts[r][c].is_none() {
+                        parts[r][c] = Some(part);
+                        stack.push((r, c));
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        (parts, sizes)
+    }
+
+
+    // returns the cells adjacent to the cell, which lie within the mask
+    fn adjacent(&self, (row, col): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        [(row.wrapping_sub(1), col), (row + 1, col), (row, col.wrapping_sub(1)), (row, col + 1)]
+            .into_iter()
+            .filter(move |&(r, c)| r < height && c < width)
+    }
+
+
+    /*
+     * Checks if all cells of the mask are reachable from each other through adjacent cells,
+     * which is the case for a mask with no cells too.
+     */
+    pub fn is_connected(&self) -> bool {
+        self.parts().1.len() <= 1
+    }
+
+
+    /*
+     * Checks if the mask has a cell at the specified row and column.
+     */
+    pub fn is_on(&self, row: usize, col: usize) -> bool {
+        self.bits[row][col]
+    }
+
+
+    /*
+     * Switches the cell at the specified row and column on or off.
+     */
+    pub fn set(&mut self, row: usize, col: usize, on: bool) {
+        self.bits[row][col] = on;
+    }
+
+
+    /*
+     * Returns the number of cells switched on.
+     */
+    pub fn count(&self) -> usize {
+        self.bits.iter().flatten().filter(|&&on| on).count()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use crate::algos::recursive_backtracking;
+    use crate::maze::grid::Grid;
+    use crate::solver::distances::Distances;
+
+
+    #[test]
+    fn mask_parse() {
+        let mask = Mask::parse("X..X\n....\n.X\n\n");
+
+        assert_eq!((4, 3), (mask.width, mask.height));
+        assert!(!mask.is_on(0, 0) && mask.is_on(0, 1));
+        // the missing characters of the short line are no cells
+        assert!(!mask.is_on(2, 1) && !mask.is_on(2, 3));
+        assert_eq!(7, mask.count());
+    }
+
+
+    #[test]
+    fn mask_from_txt() {
+        let mask = Mask::from_txt("tests/fixtures/masks/ring.txt").unwrap();
+
+        assert_eq!((7, 5), (mask.width, mask.height));
+        assert!(!mask.is_on(2, 3));
+        assert!(Mask::from_txt("tests/fixtures/masks/missing.txt").is_err());
+    }
+
+
+    #[test]
+    fn mask_from_shape() {
+        let mask = Mask::from_shape(&Shape::ring((10.0, 10.0), 10.0, 4.0), 20, 20);
+
+        assert_eq!((20, 20), (mask.width, mask.height));
+        assert!(!mask.is_on(0, 0) && !mask.is_on(10, 10));
+        assert!(mask.is_on(10, 1) && mask.is_on(3, 10));
+    }
+
+
+    #[test]
+    fn mask_from_text() {
+        let mask = Mask::from_text("Hi", 2);
+
+        assert_eq!((2 * 11, 2 * 7), (mask.width, mask.height));
+        // the stem of the 'H', and the gap between the letters
+        assert!(mask.is_on(0, 0) && mask.is_on(13, 1) && !mask.is_on(0, 2));
+        // the letters are bridged, although they do not touch each other
+        assert!(mask.is_connected());
+        assert!(mask.count() > 2 * 2 * (17 + 11));
+    }
+
+
+    #[test]
+    fn mask_from_text_negative() {
+        let mask = Mask::from_text_negative("HAPPY BIRTHDAY\n2025", 1);
+
+        assert_eq!((14 * 6 - 1 + 2, 2 * 8 - 1 + 2), (mask.width as usize, mask.height as usize));
+        // the margin surrounds the letters, while the stem of the 'H' is a hole
+        assert!(mask.is_on(0, 0) && !mask.is_on(1, 1));
+        // the holes in the letters, e.g. 'A', 'P' or '0', are bridged to the rest of the maze
+        assert!(mask.is_connected());
+    }
+
+
+    #[test]
+    fn mask_connect() {
+        let mut mask = Mask::parse("..XXX\n..XX.\nXXXXX\n.XXXX");
+        assert!(!mask.is_connected());
+        mask.connect();
+
+        assert_eq!(Mask::parse("..XXX\n.....\n.XXXX\n.XXXX"), mask);
+    }
+
+
+    #[test]
+    fn mask_from_image() {
+        // a white disc on black, inside a frame of transparent white pixels
+        let img = image::RgbaImage::from_fn(40, 40, |x, y| {
+            let (dx, dy) = (x as f64 - 19.5, y as f64 - 19.5);
+            if x < 4 || y < 4 || x >= 36 || y >= 36 {
+                image::Rgba([255, 255, 255, 0])
+            } else if dx * dx + dy * dy <= 14.0 * 14.0 {
+                image::Rgba([255, 255, 255, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            }
+        });
+        let img = DynamicImage::ImageRgba8(img);
+
+        let mask = Mask::from_image(&img, 40, 40);
+        assert!(mask.is_on(20, 20) && !mask.is_on(6, 6) && !mask.is_on(0, 20));
+
+        // the scaled mask keeps the disc in the centre of the grid
+        let mask = Mask::from_image(&img, 10, 10);
+        assert_eq!((10, 10), (mask.width, mask.height));
+        assert!(mask.is_on(5, 5) && mask.is_on(2, 5) && !mask.is_on(0, 0) && !mask.is_on(1, 1));
+    }
+
+
+    #[test]
+    fn mask_from_shape_thin() {
+        let shapes = [Shape::ring((15.0, 15.0), 14.0, 13.0), Shape::star((15.0, 15.0), 15.0, 5.0, 7)];
+
+        for shape in &shapes {
+            let mask = Mask::from_shape(shape, 30, 30);
+            assert!(mask.is_connected());
+
+            // the maze reaches every cell, even along the ring only a cell wide
+            assert!(fills(&mask));
+        }
+    }
+
+
+    #[test]
+    fn mask_from_image_blobs() {
+        // two white discs on black, far apart from each other, the left one larger
+        let img = image::GrayImage::from_fn(60, 30, |x, y| {
+            let dy = y as f64 - 14.5;
+            let inside = |cx: f64, r: f64| (x as f64 - cx).powi(2) + dy * dy <= r * r;
+            image::Luma([if inside(14.5, 11.0) || inside(44.5, 9.0) { 255 } else { 0 }])
+        });
+        let img = DynamicImage::ImageLuma8(img);
+
+        // only the larger disc is kept
+        let mask = Mask::from_image(&img, 12, 6);
+        assert!(mask.is_on(3, 3) && !mask.is_on(3, 9) && !mask.is_on(0, 0));
+        assert!(mask.is_connected());
+
+        // both discs are kept, and bridged, so a single maze fills them
+        let mask = Mask::from_image_bridged(&img, 12, 6);
+        assert!(mask.is_on(3, 3) && mask.is_on(3, 9) && !mask.is_on(0, 0));
+        assert!(fills(&mask));
+    }
+
+
+    #[test]
+    fn mask_from_image_specks() {
+        // a white square with a few stray pixels around it
+        let img = image::GrayImage::from_fn(20, 20, |x, y| {
+            let square = (5..15).contains(&x) && (5..15).contains(&y);
+            image::Luma([if square || (x, y) == (1, 1) || (x, y) == (18, 2) { 255 } else { 0 }])
+        });
+        let mask = Mask::from_image(&DynamicImage::ImageLuma8(img), 20, 20);
+
+        assert_eq!(10 * 10, mask.count());
+        assert!(!mask.is_on(1, 1) && !mask.is_on(2, 18));
+    }
+
+
+    #[test]
+    fn mask_connect_many_parts() {
+        // a grid of single cells, none of them touching another
+        let text = ["X.X.X.X.", "XXXXXXXX"].repeat(8).join("\n");
+        let mut mask = Mask::parse(&text);
+        mask.connect();
+
+        assert!(mask.is_connected());
+        assert!(fills(&mask));
+    }
+
+
+    // checks if the maze built on the mask reaches all of its cells
+    fn fills(mask: &Mask) -> bool {
+        let mut grid = Grid::with_mask(mask.clone());
+        grid.init_grid();
+        recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+
+        let distances = Distances::from(&grid, first_cell(mask));
+        grid.cells.iter().flatten().flatten().all(|cell| {
+            let c = RefCell::borrow(cell);
+            distances.get(c.row, c.col).is_some()
+        })
+    }
+
+
+    // finds the first cell of the mask, row by row
+    fn first_cell(mask: &Mask) -> (u16, u16) {
+        (0..mask.height).flat_map(|r| (0..mask.width).map(move |c| (r, c)))
+            .find(|&(r, c)| mask.is_on(r as usize, c as usize))
+            .unwrap()
+    }
+
+
+    #[test]
+    fn mask_from_img() {
+        let mask = Mask::from_img("tests/fixtures/masks/heart.png", 24, 20).unwrap();
+
+        assert_eq!((24, 20), (mask.width, mask.height));
+        assert!(mask.is_on(10, 12) && !mask.is_on(0, 0));
+        assert!(Mask::from_img("tests/fixtures/masks/missing.png", 24, 20).is_err());
+    }
+}
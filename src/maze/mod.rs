@@ -1,2 +1,30 @@
 pub mod cell;
 pub mod grid;
+pub mod mask;
+pub mod shape;
+pub mod font;
+pub mod passages;
+pub mod topology;
+pub mod cir_cell;
+pub mod circ_grid;
+pub mod hex_cell;
+pub mod hex_grid;
+pub mod tri_cell;
+pub mod tri_grid;
+pub mod upsilon_cell;
+pub mod upsilon_grid;
+pub mod weave_cell;
+pub mod weave_grid;
+pub mod level_cell;
+pub mod level_grid;
+pub mod cube_cell;
+pub mod cube_grid;
+pub mod sphere_cell;
+pub mod sphere_grid;
+pub mod voronoi_cell;
+pub mod voronoi_grid;
+pub mod penrose_cell;
+pub mod penrose_grid;
+pub mod graph_cell;
+pub mod graph_grid;
+pub mod nested_grid;
@@ -0,0 +1,204 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::maze::grid;
+use crate::maze::topology::MazeCell;
+
+
+/*
+ * The sides of a maze, through which its openings lead out.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    North,
+    South,
+    East,
+    West,
+}
+
+
+/*
+ * The openings leading out of a maze, each with the position of the cell along the side
+ * followed by the positions of the cells along the same side of the nested sub-mazes.
+ */
+pub type Openings = Vec<(Side, Vec<u16>)>;
+
+
+/*
+ * The grid of a nested maze, whose every cell holds a sub-maze of the same size, down to
+ * the specified depth. Wherever two cells are joined by a passage, their sub-mazes are opened
+ * towards each other at the same place, so the path leads through the innermost mazes.
+ */
+pub struct NestedGrid {
+    pub width: u16,
+    pub height: u16,
+    // the number of levels, including this one
+    pub depth: u16,
+
+    pub grid: grid::Grid,
+
+    pub openings: Openings,
+
+    // the sub-mazes of the cells, row by row, which the deepest level has none of
+    pub children: Vec<Vec<NestedGrid>>,
+}
+
+
+impl NestedGrid {
+    /*
+     * Creates a nested maze of the specified depth, whose every level is 'width' x 'height'
+     * cells large. Panics if the depth is zero, or if the innermost cells would not fit into
+     * a single grid, i.e. 'width ^ depth' or 'height ^ depth' exceeds 'u16::MAX'.
+     */
+    pub fn new(width: u16, height: u16, depth: u16) -> Self {
+        assert!(depth > 0, "depth must be positive");
+        assert!(width.checked_pow(depth as u32).is_some() && height.checked_pow(depth as u32).is_some(),
+                "nested maze is too large");
+
+        Self {
+            width,
+            height,
+            depth,
+
+            grid: grid::Grid::new(width, height),
+
+            openings: vec![],
+
+            children: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.grid.init_grid();
+    }
+
+
+    /*
+     * Returns the position of the opening on the side of the maze, if there is any.
+     */
+    pub fn opening(&self, side: Side) -> Option<&Vec<u16>> {
+        self.openings.iter().find(|(s, _)| *s == side).map(|(_, positions)| positions)
+    }
+
+
+    /*
+     * Converts the innermost mazes into a single rectangular maze of 'width ^ depth' x
+     * 'height ^ depth' cells, e.g. to be solved by the solvers of a 'Grid'.
+     */
+    pub fn flatten(&self) -> grid::Grid {
+        let mut flat = grid::Grid::new(self.width.pow(self.depth as u32), self.height.pow(self.depth as u32));
+        flat.init_grid();
+        self.flatten_into(&flat, (0, 0));
+
+        flat
+    }
+
+
+    // links the cells of the flat maze, whose top left cell is covered by this maze at 'origin'
+    fn flatten_into(&self, flat: &grid::Grid, origin: (usize, usize)) {
+        let link = |a: (usize, usize), b: (usize, usize)| {
+            let (a, b) = (flat.cells[a.0][a.1].as_ref().unwrap(), flat.cells[b.0][b.1].as_ref().unwrap());
+            RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+            RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+        };
+
+        // the number of flat cells covered by each cell of this maze
+        let (w, h) = (self.width.pow(self.depth as u32 - 1) as usize, self.height.pow(self.depth as u32 - 1) as usize);
+        // the offset of the innermost cell along the side, reached through the positions
+        let offset = |positions: &[u16], len: u16| positions.iter().enumerate()
+            .map(|(i, &k)| k as usize * (len as usize).pow(self.depth as u32 - 2 - i as u32))
+            .sum::<usize>();
+
+        for cell in self.grid.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            let (row, col) = (origin.0 + c.row as usize * h, origin.1 + c.col as usize * w);
+
+            if self.depth == 1 {
+                for n in c.links() {
+                    let n = n.upgrade().unwrap();
+                    let n = RefCell::borrow(&n);
+                    if (n.row, n.col) > (c.row, c.col) {
+                        link((row, col), (origin.0 + n.row as usize, origin.1 + n.col as usize));
+                    }
+                }
+                continue;
+            }
+
+            let child = &self.children[c.row as usize][c.col as usize];
+            child.flatten_into(flat, (row, col));
+
+            // the passages to the right and the bottom cell lead through the doors of the sub-mazes
+            if let Some(positions) = child.opening(Side::East) {
+                if c.col + 1 < self.width {
+                    let r = row + offset(positions, self.height);
+                    link((r, col + w - 1), (r, col + w));
+                }
+            }
+            if let Some(positions) = child.opening(Side::South) {
+                if c.row + 1 < self.height {
+                    let k = col + offset(positions, self.width);
+                    link((row + h - 1, k), (row + h, k));
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, SeedableRng, rngs::StdRng};
+    use crate::Algo;
+    use crate::algos::nested::build_maze;
+    use crate::solver::distances::Distances;
+
+
+    #[test]
+    fn nested_grid_openings_match() {
+        let mut grid = NestedGrid::new(3, 4, 3);
+        grid.init_grid();
+        build_maze(&Algo::RecursiveBacktracking, &mut grid, &mut thread_rng());
+
+        for cell in grid.grid.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            let child = &grid.children[c.row as usize][c.col as usize];
+            assert_eq!(2, child.depth);
+            assert_eq!(c.links().len(), child.openings.len());
+
+            if let Some(right) = c.right.as_ref().map(|r| r.upgrade().unwrap()) {
+                let neighbour = &grid.children[c.row as usize][c.col as usize + 1];
+                assert_eq!(c.is_linked_to(Rc::downgrade(&right)), child.opening(Side::East).is_some());
+                assert_eq!(child.opening(Side::East), neighbour.opening(Side::West));
+            }
+        }
+    }
+
+
+    #[test]
+    fn nested_grid_flatten_perfect() {
+        for algo in [Algo::RecursiveBacktracking, Algo::Sidewinder] {
+            let mut grid = NestedGrid::new(3, 2, 3);
+            grid.init_grid();
+            build_maze(&algo, &mut grid, &mut StdRng::seed_from_u64(7));
+
+            let flat = grid.flatten();
+            assert_eq!((27, 8), (flat.width, flat.height));
+
+            // the innermost mazes and the doors between them form a single perfect maze
+            let links: usize = flat.cells.iter().flatten().flatten()
+                .map(|cell| RefCell::borrow(cell).links().len())
+                .sum();
+            assert_eq!(2 * (27 * 8 - 1), links);
+            let distances = Distances::from(&flat, (0, 0));
+            assert!((0..8).all(|r| (0..27).all(|c| distances.get(r, c).is_some())));
+        }
+    }
+
+
+    #[test]
+    #[should_panic(expected = "nested maze is too large")]
+    fn nested_grid_too_large() {
+        NestedGrid::new(20, 20, 4);
+    }
+}
@@ -0,0 +1,158 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::maze::grid;
+use crate::maze::topology::MazeCell;
+
+
+pub(crate) const EAST: u8 = 0b01;
+pub(crate) const SOUTH: u8 = 0b10;
+
+
+/*
+ * A compact representation of a rectangular maze, storing only the passages leading east
+ * and south from each cell. Unlike 'Grid', it can be shared among threads and keeps
+ * a single byte per cell, hence it is used for mazes too large to be built cell by cell.
+ */
+pub struct Passages {
+    pub width: u32,
+    pub height: u32,
+
+    cells: Vec<u8>,
+}
+
+
+impl Passages {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![0; width as usize * height as usize],
+        }
+    }
+
+
+    fn index(&self, row: u32, col: u32) -> usize {
+        row as usize * self.width as usize + col as usize
+    }
+
+
+    /*
+     * Checks if there is a passage from the specified cell to its right neighbour.
+     */
+    pub fn is_linked_east(&self, row: u32, col: u32) -> bool {
+        self.cells[self.index(row, col)] & EAST != 0
+    }
+
+
+    /*
+     * Checks if there is a passage from the specified cell to its bottom neighbour.
+     */
+    pub fn is_linked_south(&self, row: u32, col: u32) -> bool {
+        self.cells[self.index(row, col)] & SOUTH != 0
+    }
+
+
+    /*
+     * Creates a passage from the specified cell to its right neighbour.
+     */
+    pub fn link_east(&mut self, row: u32, col: u32) {
+        let i = self.index(row, col);
+        self.cells[i] |= EAST;
+    }
+
+
+    /*
+     * Creates a passage from the specified cell to its bottom neighbour.
+     */
+    pub fn link_south(&mut self, row: u32, col: u32) {
+        let i = self.index(row, col);
+        self.cells[i] |= SOUTH;
+    }
+
+
+    /*
+     * Returns the total number of passages in the maze.
+     */
+    pub fn count_passages(&self) -> usize {
+        self.cells.iter().map(|c| c.count_ones() as usize).sum()
+    }
+
+
+    // gives mutable access to the raw cells, one row after another
+    pub(crate) fn cells_mut(&mut self) -> &mut [u8] {
+        &mut self.cells
+    }
+
+
+    /*
+     * Converts the maze into a 'Grid', so that it can be used with the presenters.
+     * Panics if the maze does not fit into a 'Grid'.
+     */
+    pub fn to_grid(&self) -> grid::Grid {
+        let mut grid = grid::Grid::new(
+            u16::try_from(self.width).expect("maze is too wide to be converted into a grid"),
+            u16::try_from(self.height).expect("maze is too high to be converted into a grid"));
+        grid.init_grid();
+
+        for row in &grid.cells {
+            for cell in row.iter() {
+                let cell = cell.as_ref().unwrap();
+                let (r, c) = {
+                    let c = RefCell::borrow(cell);
+                    (c.row as u32, c.col as u32)
+                };
+
+                let mut neighbours = vec![];
+                if c + 1 < self.width && self.is_linked_east(r, c) {
+                    neighbours.push(RefCell::borrow(cell).right.as_ref().unwrap().upgrade().unwrap());
+                }
+                if r + 1 < self.height && self.is_linked_south(r, c) {
+                    neighbours.push(RefCell::borrow(cell).bottom.as_ref().unwrap().upgrade().unwrap());
+                }
+
+                for neighbour in neighbours {
+                    RefCell::borrow_mut(cell).link_to(Rc::downgrade(&neighbour));
+                    RefCell::borrow_mut(&neighbour).link_to(Rc::downgrade(cell));
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn passages_link() {
+        let mut passages = Passages::new(4, 3);
+        passages.link_east(1, 2);
+        passages.link_south(1, 2);
+
+        assert!(passages.is_linked_east(1, 2));
+        assert!(passages.is_linked_south(1, 2));
+        assert!(!passages.is_linked_east(2, 1));
+        assert_eq!(2, passages.count_passages());
+    }
+
+
+    #[test]
+    fn passages_to_grid() {
+        let mut passages = Passages::new(3, 2);
+        passages.link_east(0, 0);
+        passages.link_south(0, 0);
+
+        let grid = passages.to_grid();
+        let cell = RefCell::borrow(grid.cells[0][0].as_ref().unwrap());
+        let right = grid.cells[0][1].as_ref().unwrap();
+        let bottom = grid.cells[1][0].as_ref().unwrap();
+
+        assert!(cell.is_linked_to(Rc::downgrade(right)));
+        assert!(RefCell::borrow(bottom).is_linked_to(grid.cells[0][0].as_ref().map(Rc::downgrade).unwrap()));
+    }
+}
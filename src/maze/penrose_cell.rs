@@ -0,0 +1,62 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type PenroseCellWeakLink = Weak<RefCell<PenroseCell>>;
+pub type PenroseCellStrongLink = Rc<RefCell<PenroseCell>>;
+
+
+/*
+ * The PenroseCell class used to represent a rhomb of the Penrose tiling. Each side of
+ * the rhomb, leading from one corner to the next, is shared with at most one other rhomb.
+ */
+pub struct PenroseCell {
+    pub index: u32,
+    // the thin rhombs have the angles of 36 and 144 degrees, the thick ones of 72 and 108
+    pub thin: bool,
+    pub corners: [(f64, f64); 4],
+
+    links: Vec<PenroseCellWeakLink>,
+
+    pub sides: [Option<PenroseCellWeakLink>; 4],
+}
+
+impl PartialEq for PenroseCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+
+impl PenroseCell {
+    pub fn new(index: u32, thin: bool, corners: [(f64, f64); 4]) -> Self {
+        Self {
+            index,
+            thin,
+            corners,
+
+            links: vec![],
+
+            sides: [None, None, None, None],
+        }
+    }
+}
+
+
+impl MazeCell for PenroseCell {
+    fn link_list(&self) -> &Vec<PenroseCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<PenroseCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<PenroseCellWeakLink> {
+        self.sides.iter().flatten().map(Weak::clone).collect()
+    }
+}
@@ -0,0 +1,215 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::penrose_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+type Point = (f64, f64);
+
+
+/*
+ * The golden ratio, which the sides of the Robinson triangles are in.
+ */
+const PHI: f64 = 1.618_033_988_749_895;
+
+
+// a half of a rhomb, split along its shorter diagonal from 'b' to 'c', whose apex 'a'
+// has the angle of 36 degrees in a thin rhomb, and 108 degrees in a thick one
+#[derive(Clone, Copy)]
+struct Triangle {
+    thin: bool,
+    a: Point,
+    b: Point,
+    c: Point,
+}
+
+
+/*
+ * The grid of a maze on the Penrose tiling of thin and thick rhombs, grown from a wheel
+ * of ten triangles around the centre of the unit circle, which are subdivided 'depth' times.
+ * The triangles pair up into rhombs along their bases, except for the ones along the rim
+ * of the wheel, which are left out. The rhombs sharing a side are adjacent.
+ */
+pub struct PenroseGrid {
+    pub depth: u16,
+
+    pub cells: Vec<penrose_cell::PenroseCellStrongLink>,
+}
+
+impl PenroseGrid {
+    pub fn new(depth: u16) -> Self {
+        Self {
+            depth,
+
+            cells: vec![],
+        }
+    }
+
+
+    /*
+     * Returns the length of the sides of the rhombs, relative to the radius of the wheel.
+     */
+    pub fn side(&self) -> f64 {
+        PHI.powi(-(self.depth as i32))
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        let mut triangles: Vec<Triangle> = (0..10)
+            .map(|i| {
+                let b = polar((2 * i - 1) as f64 * PI / 10.0);
+                let c = polar((2 * i + 1) as f64 * PI / 10.0);
+                // mirrors every other triangle, so the neighbouring ones share their sides
+                let (b, c) = if i % 2 == 0 { (c, b) } else { (b, c) };
+                Triangle { thin: true, a: (0.0, 0.0), b, c }
+            })
+            .collect();
+
+        for _ in 0..self.depth {
+            triangles = triangles.iter().flat_map(subdivide).collect();
+        }
+
+        // pairs the triangles sharing their bases into rhombs
+        let mut halves: HashMap<(Key, Key), Triangle> = HashMap::new();
+        for t in triangles {
+            let base = ordered(key(t.b), key(t.c));
+            if let Some(other) = halves.remove(&base) {
+                let index = self.cells.len() as u32;
+                self.cells.push(Rc::new(RefCell::new(penrose_cell::PenroseCell::new(
+                    index, t.thin, [t.a, t.b, other.a, t.c]))));
+            } else {
+                halves.insert(base, t);
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        let mut sides: HashMap<(Key, Key), (usize, usize)> = HashMap::new();
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            let corners = RefCell::borrow(cell).corners;
+
+            for k in 0..4 {
+                let side = ordered(key(corners[k]), key(corners[(k + 1) % 4]));
+
+                if let Some((j, l)) = sides.remove(&side) {
+                    RefCell::borrow_mut(cell).sides[k] = Some(Rc::downgrade(&self.cells[j]));
+                    RefCell::borrow_mut(&self.cells[j]).sides[l] = Some(Rc::downgrade(cell));
+                } else {
+                    sides.insert(side, (i, k));
+                }
+            }
+        }
+    }
+}
+
+
+// splits the triangle into smaller ones of the next generation
+fn subdivide(t: &Triangle) -> Vec<Triangle> {
+    let Triangle { a, b, c, .. } = *t;
+
+    if t.thin {
+        let p = lerp(a, b, 1.0 / PHI);
+        vec![Triangle { thin: true, a: c, b: p, c: b },
+             Triangle { thin: false, a: p, b: c, c: a }]
+    } else {
+        let q = lerp(b, a, 1.0 / PHI);
+        let r = lerp(b, c, 1.0 / PHI);
+        vec![Triangle { thin: false, a: r, b: c, c: a },
+             Triangle { thin: false, a: q, b: r, c: b },
+             Triangle { thin: true, a: r, b: q, c: a }]
+    }
+}
+
+
+fn polar(angle: f64) -> Point {
+    (angle.cos(), angle.sin())
+}
+
+
+fn lerp(from: Point, to: Point, t: f64) -> Point {
+    (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t)
+}
+
+
+// the corners shared by the triangles are computed along different paths, hence they are
+// compared after rounding
+type Key = (i64, i64);
+
+fn key(p: Point) -> Key {
+    ((p.0 * 1e7).round() as i64, (p.1 * 1e7).round() as i64)
+}
+
+
+fn ordered(a: Key, b: Key) -> (Key, Key) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+
+impl MazeGrid for PenroseGrid {
+    type Cell = penrose_cell::PenroseCell;
+
+
+    fn each_cell(&self) -> Vec<penrose_cell::PenroseCellStrongLink> {
+        self.cells.iter().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> penrose_cell::PenroseCellWeakLink {
+        Rc::downgrade(sample::choose(rng, &self.cells).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+    use crate::algos::{kruskal, recursive_backtracking};
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn penrose_grid_rhombs() {
+        let mut grid = PenroseGrid::new(4);
+        grid.init_grid();
+
+        let side = grid.side();
+        for cell in &grid.cells {
+            let c = RefCell::borrow(cell);
+            for k in 0..4 {
+                let (p, q) = (c.corners[k], c.corners[(k + 1) % 4]);
+                let length = ((p.0 - q.0).powi(2) + (p.1 - q.1).powi(2)).sqrt();
+                assert!((length - side).abs() < 1e-9);
+            }
+        }
+        assert!(grid.cells.iter().any(|c| RefCell::borrow(c).thin));
+        assert!(grid.cells.iter().any(|c| !RefCell::borrow(c).thin));
+    }
+
+
+    #[test]
+    fn penrose_grid_perfect_maze() {
+        for build in [recursive_backtracking::build_maze::<PenroseGrid, _>,
+                      kruskal::build_maze::<PenroseGrid, _>] {
+            let mut grid = PenroseGrid::new(4);
+            grid.init_grid();
+
+            build(&grid, &mut thread_rng());
+
+            let links: usize = grid.cells.iter().map(|c| RefCell::borrow(c).links().len()).sum();
+            assert_eq!(2 * (grid.cells.len() - 1), links);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::f64::consts::PI;
+
+
+/*
+ * The outline of a maze, measured in cells from the top left corner of the grid, with
+ * the rows growing downwards. A cell belongs to the maze, when its centre lies inside.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    // the vertices of a simple polygon, in either order
+    Polygon(Vec<(f64, f64)>),
+    Circle { centre: (f64, f64), radius: f64 },
+    // a rectangle of the specified corner, size, and radius of the rounded corners
+    RoundedRect { corner: (f64, f64), size: (f64, f64), radius: f64 },
+    // the first shape with all the others cut out of it
+    Cutout(Box<Shape>, Vec<Shape>),
+}
+
+
+impl Shape {
+    /*
+     * Creates a star of the specified number of points, whose tips lie on the outer circle,
+     * and whose inner vertices lie on the inner circle. The first point faces upwards.
+     */
+    pub fn star(centre: (f64, f64), outer: f64, inner: f64, points: u16) -> Self {
+        let vertices = (0..2 * points).map(|i| {
+            let radius = if i % 2 == 0 { outer } else { inner };
+            let angle = PI * i as f64 / points as f64 - PI / 2.0;
+            (centre.0 + radius * angle.cos(), centre.1 + radius * angle.sin())
+        }).collect();
+
+        Shape::Polygon(vertices)
+    }
+
+
+    /*
+     * Creates a ring between the two circles around the centre.
+     */
+    pub fn ring(centre: (f64, f64), outer: f64, inner: f64) -> Self {
+        Shape::Circle { centre, radius: outer }.with_hole(Shape::Circle { centre, radius: inner })
+    }
+
+
+    /*
+     * Cuts the hole out of the shape.
+     */
+    pub fn with_hole(self, hole: Shape) -> Self {
+        match self {
+            Shape::Cutout(outline, mut holes) => {
+                holes.push(hole);
+                Shape::Cutout(outline, holes)
+            }
+            outline => Shape::Cutout(Box::new(outline), vec![hole]),
+        }
+    }
+
+
+    /*
+     * Checks if the point lies inside the shape.
+     */
+    pub fn contains(&self, (x, y): (f64, f64)) -> bool {
+        match self {
+            Shape::Polygon(vertices) => {
+                // counts the edges crossed by a ray from the point to the right
+                let mut inside = false;
+                for (i, &(x1, y1)) in vertices.iter().enumerate() {
+                    let (x2, y2) = vertices[(i + 1) % vertices.len()];
+                    if (y1 > y) != (y2 > y) && x < x1 + (y - y1) / (y2 - y1) * (x2 - x1) {
+                        inside = !inside;
+                    }
+                }
+                inside
+            }
+            Shape::Circle { centre, radius } => {
+                (x - centre.0).powi(2) + (y - centre.1).powi(2) <= radius * radius
+            }
+            Shape::RoundedRect { corner, size, radius } => {
+                // the distance from the rectangle shrunk by the radius on all sides
+                let radius = radius.min(size.0 / 2.0).min(size.1 / 2.0);
+                let dx = (corner.0 + radius - x).max(x - (corner.0 + size.0 - radius)).max(0.0);
+                let dy = (corner.1 + radius - y).max(y - (corner.1 + size.1 - radius)).max(0.0);
+                dx * dx + dy * dy <= radius * radius
+            }
+            Shape::Cutout(outline, holes) => {
+                outline.contains((x, y)) && !holes.iter().any(|hole| hole.contains((x, y)))
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn shape_polygon() {
+        let triangle = Shape::Polygon(vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+
+        assert!(triangle.contains((1.0, 1.0)));
+        assert!(!triangle.contains((3.0, 3.0)));
+        assert!(!triangle.contains((-1.0, 1.0)));
+    }
+
+
+    #[test]
+    fn shape_star() {
+        let star = Shape::star((0.0, 0.0), 10.0, 4.0, 5);
+
+        // the top point is inside, while the gap between the two top points is not
+        assert!(star.contains((0.0, -9.0)) && star.contains((0.0, 0.0)));
+        assert!(!star.contains((4.0, -7.0)));
+    }
+
+
+    #[test]
+    fn shape_rounded_rect() {
+        let rect = Shape::RoundedRect { corner: (0.0, 0.0), size: (10.0, 6.0), radius: 2.0 };
+
+        assert!(rect.contains((5.0, 0.5)) && rect.contains((9.5, 3.0)));
+        assert!(!rect.contains((0.2, 0.2)) && !rect.contains((11.0, 3.0)));
+    }
+
+
+    #[test]
+    fn shape_ring_with_holes() {
+        let ring = Shape::ring((0.0, 0.0), 10.0, 5.0)
+            .with_hole(Shape::Circle { centre: (7.5, 0.0), radius: 1.0 });
+
+        assert!(ring.contains((0.0, 7.5)));
+        assert!(!ring.contains((0.0, 0.0)) && !ring.contains((7.5, 0.0)));
+        assert!(matches!(&ring, Shape::Cutout(_, holes) if holes.len() == 2));
+    }
+}
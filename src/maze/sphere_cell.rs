@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type SphereCellWeakLink = Weak<RefCell<SphereCell>>;
+pub type SphereCellStrongLink = Rc<RefCell<SphereCell>>;
+
+
+/*
+ * The SphereCell class used to represent a cell in the spherical maze. The row is the index
+ * of the latitude band, counted from the north pole, and the column is the index of the cell
+ * in the band, counted eastwards from the longitude seam.
+ */
+pub struct SphereCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<SphereCellWeakLink>,
+
+    pub east: Option<SphereCellWeakLink>,
+    pub west: Option<SphereCellWeakLink>,
+    // the bands hold different numbers of cells, so a cell may border several cells
+    // of the bands next to it
+    pub north: Vec<SphereCellWeakLink>,
+    pub south: Vec<SphereCellWeakLink>,
+}
+
+impl PartialEq for SphereCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl SphereCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            east: None,
+            west: None,
+            north: vec![],
+            south: vec![],
+        }
+    }
+}
+
+
+impl MazeCell for SphereCell {
+    fn link_list(&self) -> &Vec<SphereCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<SphereCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<SphereCellWeakLink> {
+        [&self.east, &self.west]
+            .into_iter()
+            .flatten()
+            .chain(self.north.iter())
+            .chain(self.south.iter())
+            .map(Weak::clone)
+            .collect()
+    }
+}
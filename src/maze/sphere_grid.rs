@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::sphere_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a spherical maze, made of latitude bands of equal height from pole to pole.
+ * The number of cells in each band follows the length of its circle of latitude, so the cells
+ * remain roughly as wide as they are high, and the last cell of a band joins the first one
+ * across the longitude seam.
+ */
+pub struct SphereGrid {
+    pub rows: u16,
+
+    pub cells: Vec<Vec<Option<sphere_cell::SphereCellStrongLink>>>,
+}
+
+impl SphereGrid {
+    pub fn new(rows: u16) -> Self {
+        Self {
+            rows,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        let band_height = PI / self.rows as f64;
+
+        for row in 0..self.rows as usize {
+            // the equator is twice as long as the meridian from pole to pole, and the bands
+            // hold at least 3 cells, so no two cells are adjacent on both sides
+            let latitude = PI / 2.0 - (row as f64 + 0.5) * band_height;
+            let count = ((2.0 * PI * latitude.cos() / band_height).round() as usize).max(3);
+
+            self.cells.push((0..count)
+                .map(|col| Some(Rc::new(RefCell::new(sphere_cell::SphereCell::new(row as u16, col as u16)))))
+                .collect());
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for row in 0..self.rows as usize {
+            let count = self.cells[row].len();
+
+            for col in 0..count {
+                let cell = self.cells[row][col].as_ref().unwrap();
+
+                RefCell::borrow_mut(cell).east = Some(Rc::downgrade(
+                    self.cells[row][(col + 1) % count].as_ref().unwrap()));
+                RefCell::borrow_mut(cell).west = Some(Rc::downgrade(
+                    self.cells[row][(col + count - 1) % count].as_ref().unwrap()));
+
+                if row + 1 < self.rows as usize {
+                    for other in self.overlapping(row, col, row + 1) {
+                        let south = self.cells[row + 1][other].as_ref().unwrap();
+                        RefCell::borrow_mut(cell).south.push(Rc::downgrade(south));
+                        RefCell::borrow_mut(south).north.push(Rc::downgrade(cell));
+                    }
+                }
+            }
+        }
+    }
+
+
+    /*
+     * Returns the range of longitudes covered by the cell, as fractions of the full circle.
+     */
+    pub fn longitudes(&self, row: usize, col: usize) -> (f64, f64) {
+        let count = self.cells[row].len() as f64;
+        (col as f64 / count, (col + 1) as f64 / count)
+    }
+
+
+    // returns the columns of the cells in the 'other' band sharing a border with the cell
+    fn overlapping(&self, row: usize, col: usize, other: usize) -> Vec<usize> {
+        // compares the ranges of longitudes using a common denominator
+        let (count, other_count) = (self.cells[row].len(), self.cells[other].len());
+        let (start, end) = (col * other_count, (col + 1) * other_count);
+
+        (start / count..=(end - 1) / count)
+            .filter(|c| c * count < end && start < (c + 1) * count)
+            .collect()
+    }
+
+
+    /*
+     * Returns the total number of cells.
+     */
+    pub fn size(&self) -> usize {
+        self.cells.iter().map(Vec::len).sum()
+    }
+}
+
+
+impl MazeGrid for SphereGrid {
+    type Cell = sphere_cell::SphereCell;
+
+
+    fn each_cell(&self) -> Vec<sphere_cell::SphereCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> sphere_cell::SphereCellWeakLink {
+        // every cell has the same chance to be picked, regardless of the size of its band
+        let mut index = sample::gen_index(rng, self.size());
+        for row in &self.cells {
+            if index < row.len() {
+                return Rc::downgrade(row[index].as_ref().unwrap());
+            }
+            index -= row.len();
+        }
+
+        unreachable!()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn sphere_grid_bands_shrink_towards_poles() {
+        let mut grid = SphereGrid::new(10);
+        grid.init_grid();
+
+        assert_eq!(grid.cells[0].len(), grid.cells[9].len());
+        assert!(grid.cells[0].len() < grid.cells[4].len());
+        assert_eq!(20, grid.cells[4].len());
+    }
+
+
+    #[test]
+    fn sphere_grid_crosses_seam() {
+        let mut grid = SphereGrid::new(6);
+        grid.init_grid();
+
+        let last = grid.cells[2].len() - 1;
+        let cell = RefCell::borrow(grid.cells[2][last].as_ref().unwrap());
+        let east = RefCell::borrow(&cell.east.as_ref().unwrap().upgrade().unwrap()).col;
+
+        assert_eq!(0, east);
+    }
+
+
+    #[test]
+    fn sphere_grid_neighbours_are_mutual() {
+        let mut grid = SphereGrid::new(9);
+        grid.init_grid();
+
+        for cell in grid.each_cell() {
+            let c = RefCell::borrow(&cell);
+            assert!(c.row == 0 || !c.north.is_empty());
+            assert!(c.row == 8 || !c.south.is_empty());
+
+            for neighbour in c.neighbours() {
+                let n = neighbour.upgrade().unwrap();
+                assert!(RefCell::borrow(&n).neighbours().iter()
+                    .any(|m| *RefCell::borrow(&m.upgrade().unwrap()) == *c));
+            }
+        }
+    }
+}
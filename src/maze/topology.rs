@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use rand::Rng;
+
+
+/*
+ * The interface shared by the cells of all maze topologies. Each cell keeps the list of cells
+ * it is linked to, and knows its adjacent neighbours, however many of them there are.
+ * The generation algorithms only relying on this interface work on any topology.
+ */
+pub trait MazeCell: PartialEq + Sized {
+    /*
+     * Returns the list of links to the adjacent cells.
+     */
+    fn link_list(&self) -> &Vec<Weak<RefCell<Self>>>;
+
+
+    /*
+     * Returns the mutable list of links to the adjacent cells.
+     */
+    fn link_list_mut(&mut self) -> &mut Vec<Weak<RefCell<Self>>>;
+
+
+    /*
+     * Returns a list of all adjacent neighbours.
+     */
+    fn neighbours(&self) -> Vec<Weak<RefCell<Self>>>;
+
+
+    /*
+     * Creates a link to the specified adjacent cell.
+     */
+    fn link_to(&mut self, cell: Weak<RefCell<Self>>) {
+        self.link_list_mut().push(Rc::downgrade(&cell.upgrade().unwrap()));
+    }
+
+
+    /*
+     * Removes the link to the specified adjacent cell, if there is any.
+     */
+    fn unlink_from(&mut self, cell: Weak<RefCell<Self>>) {
+        let cell = cell.upgrade().unwrap();
+        self.link_list_mut().retain(|link| *link.upgrade().unwrap().borrow() != *cell.borrow());
+    }
+
+
+    /*
+     * Returns a list of all linked cells.
+     */
+    fn links(&self) -> Vec<Weak<RefCell<Self>>> {
+        self.link_list().iter().map(Weak::clone).collect()
+    }
+
+
+    /*
+     * Checks if it is linked to any adjacent cell in the maze.
+     */
+    fn has_linked_cells(&self) -> bool {
+        !self.link_list().is_empty()
+    }
+
+
+    /*
+     * Checks if it is linked to the specified cell.
+     */
+    fn is_linked_to(&self, cell: Weak<RefCell<Self>>) -> bool {
+        let cell = cell.upgrade().unwrap();
+        self.link_list().iter().any(|link| *link.upgrade().unwrap().borrow() == *cell.borrow())
+    }
+
+
+    /*
+     * Checks if a passage to the specified adjacent cell can be created, which is always
+     * the case unless the topology lets passages cross each other.
+     */
+    fn can_link_to(&self, _cell: Weak<RefCell<Self>>) -> bool {
+        true
+    }
+}
+
+
+/*
+ * The interface shared by the grids of all maze topologies.
+ */
+pub trait MazeGrid {
+    type Cell: MazeCell;
+
+
+    /*
+     * Returns a list of all cells of the grid.
+     */
+    fn each_cell(&self) -> Vec<Rc<RefCell<Self::Cell>>>;
+
+
+    /*
+     * Returns a random cell of the grid.
+     */
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> Weak<RefCell<Self::Cell>>;
+}
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type TriCellWeakLink = Weak<RefCell<TriCell>>;
+pub type TriCellStrongLink = Rc<RefCell<TriCell>>;
+
+
+/*
+ * The TriCell class used to represent a triangular cell in the delta maze. The cells point
+ * up and down alternately, so each one has either a neighbour on its north or on its south.
+ */
+pub struct TriCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<TriCellWeakLink>,
+
+    pub north: Option<TriCellWeakLink>,
+    pub south: Option<TriCellWeakLink>,
+    pub east: Option<TriCellWeakLink>,
+    pub west: Option<TriCellWeakLink>,
+}
+
+impl PartialEq for TriCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl TriCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+        }
+    }
+
+
+    /*
+     * Checks if the cell points up, i.e. it has its base on the south.
+     */
+    pub fn is_upright(&self) -> bool {
+        (self.row + self.col).is_multiple_of(2)
+    }
+}
+
+
+impl MazeCell for TriCell {
+    fn link_list(&self) -> &Vec<TriCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<TriCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<TriCellWeakLink> {
+        [&self.north, &self.south, &self.east, &self.west]
+            .into_iter()
+            .flatten()
+            .map(Weak::clone)
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn tri_cell_upright() {
+        assert!(TriCell::new(0, 0).is_upright());
+        assert!(!TriCell::new(0, 1).is_upright());
+        assert!(!TriCell::new(1, 0).is_upright());
+    }
+}
@@ -0,0 +1,195 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::tri_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a delta maze made of triangles pointing up and down alternately. The grid may
+ * take any overall shape, in which case the cells outside of the shape are left out.
+ */
+pub struct TriGrid {
+    pub width: u16,
+    pub height: u16,
+
+    pub cells: Vec<Vec<Option<tri_cell::TriCellStrongLink>>>,
+
+    shape: Box<dyn Fn(u16, u16) -> bool>,
+}
+
+impl TriGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self::with_shape(width, height, |_, _| true)
+    }
+
+
+    /*
+     * Creates a grid, which only has the cells at the rows and columns the shape contains.
+     */
+    pub fn with_shape<S: Fn(u16, u16) -> bool + 'static>(width: u16, height: u16, shape: S) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![],
+
+            shape: Box::new(shape),
+        }
+    }
+
+
+    /*
+     * Creates a grid in the shape of a large triangle pointing up, whose sides are 'size'
+     * cells long. Panics if the size is zero.
+     */
+    pub fn triangle(size: u16) -> Self {
+        assert!(size > 0, "size must be positive");
+
+        // the apex has to point up as well, hence it lies on an even column
+        let apex = size - 1 + (size - 1) % 2;
+
+        Self::with_shape(apex + size, size, move |row, col| {
+            apex - row <= col && col <= apex + row
+        })
+    }
+
+
+    /*
+     * Creates a grid in the shape of a regular hexagon, whose sides are 'size' cells long.
+     * Panics if the size is zero.
+     */
+    pub fn hexagon(size: u16) -> Self {
+        assert!(size > 0, "size must be positive");
+
+        // the rows of the upper half start with a triangle pointing up, hence the centre
+        // shall lie on a column of the same parity as the size
+        let centre = 2 * size - 1 + (size - 1) % 2;
+
+        Self::with_shape(centre + 2 * size, 2 * size, move |row, col| {
+            // the half of the row length, not counting the triangle in the centre
+            let half = if row < size { size + row } else { 3 * size - 1 - row };
+            centre - half <= col && col <= centre + half
+        })
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for row in 0..self.height {
+            self.cells.push(vec![]);
+            for col in 0..self.width {
+                let cell = if (self.shape)(row, col) {
+                    Some(Rc::new(RefCell::new(tri_cell::TriCell::new(row, col))))
+                } else {
+                    None
+                };
+                self.cells[row as usize].push(cell);
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for cell in self.cells.iter().flatten().flatten() {
+            let mut c = RefCell::borrow_mut(cell);
+            let row_ = c.row as i32;
+            let col_ = c.col as i32;
+
+            c.west = self.create_neighbour(row_, col_ - 1);
+            c.east = self.create_neighbour(row_, col_ + 1);
+            if c.is_upright() {
+                c.south = self.create_neighbour(row_ + 1, col_);
+            } else {
+                c.north = self.create_neighbour(row_ - 1, col_);
+            }
+        }
+    }
+
+
+    fn create_neighbour(&self, row: i32, col: i32) -> Option<tri_cell::TriCellWeakLink> {
+        if 0 <= row && row < self.height as i32 && 0 <= col && col < self.width as i32 {
+            return self.cells[row as usize][col as usize].as_ref().map(Rc::downgrade);
+        };
+        None
+    }
+}
+
+
+impl MazeGrid for TriGrid {
+    type Cell = tri_cell::TriCell;
+
+
+    fn each_cell(&self) -> Vec<tri_cell::TriCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> tri_cell::TriCellWeakLink {
+        Rc::downgrade(sample::choose(rng, &self.each_cell()).expect("grid has no cells"))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn tri_grid_neighbours() {
+        let mut grid = TriGrid::new(6, 4);
+        grid.init_grid();
+
+        let up = RefCell::borrow(grid.cells[1][1].as_ref().unwrap());
+        let down = RefCell::borrow(grid.cells[1][2].as_ref().unwrap());
+
+        assert!(up.south.is_some() && up.north.is_none());
+        assert!(down.north.is_some() && down.south.is_none());
+        assert_eq!(3, up.neighbours().len());
+    }
+
+
+    #[test]
+    fn tri_grid_triangle() {
+        for size in 1..6 {
+            let mut grid = TriGrid::triangle(size);
+            grid.init_grid();
+
+            // a triangle is made of 'size * size' triangles, and all of its corners point up
+            assert_eq!((size * size) as usize, grid.each_cell().len());
+            let last_row = grid.cells[size as usize - 1].iter().flatten().collect::<Vec<_>>();
+            assert!(RefCell::borrow(last_row[0]).is_upright());
+            assert!(RefCell::borrow(last_row[last_row.len() - 1]).is_upright());
+        }
+    }
+
+
+    #[test]
+    fn tri_grid_hexagon() {
+        for size in 1..6 {
+            let mut grid = TriGrid::hexagon(size);
+            grid.init_grid();
+
+            // a hexagon is made of six triangles of 'size * size' triangles
+            assert_eq!(6 * (size * size) as usize, grid.each_cell().len());
+            let first_row = grid.cells[0].iter().flatten().collect::<Vec<_>>();
+            assert!(RefCell::borrow(first_row[0]).is_upright());
+            assert_eq!(2 * size as usize + 1, first_row.len());
+        }
+    }
+
+
+    #[test]
+    #[should_panic(expected = "size must be positive")]
+    fn tri_grid_empty_shape() {
+        TriGrid::hexagon(0);
+    }
+}
@@ -0,0 +1,103 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type UpsilonCellWeakLink = Weak<RefCell<UpsilonCell>>;
+pub type UpsilonCellStrongLink = Rc<RefCell<UpsilonCell>>;
+
+
+/*
+ * The UpsilonCell class used to represent a cell in the upsilon maze. The cells alternate
+ * like the fields of a chessboard between octagons, which are adjacent to the cells in all
+ * eight directions, and squares, which only have the four orthogonal neighbours.
+ */
+pub struct UpsilonCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<UpsilonCellWeakLink>,
+
+    pub north: Option<UpsilonCellWeakLink>,
+    pub south: Option<UpsilonCellWeakLink>,
+    pub east: Option<UpsilonCellWeakLink>,
+    pub west: Option<UpsilonCellWeakLink>,
+
+    // only the octagons have diagonal neighbours
+    pub northeast: Option<UpsilonCellWeakLink>,
+    pub northwest: Option<UpsilonCellWeakLink>,
+    pub southeast: Option<UpsilonCellWeakLink>,
+    pub southwest: Option<UpsilonCellWeakLink>,
+}
+
+impl PartialEq for UpsilonCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col
+    }
+}
+
+
+impl UpsilonCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+
+            northeast: None,
+            northwest: None,
+            southeast: None,
+            southwest: None,
+        }
+    }
+
+
+    /*
+     * Checks if the cell is an octagon, otherwise it is a square.
+     */
+    pub fn is_octagon(&self) -> bool {
+        (self.row + self.col).is_multiple_of(2)
+    }
+}
+
+
+impl MazeCell for UpsilonCell {
+    fn link_list(&self) -> &Vec<UpsilonCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<UpsilonCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<UpsilonCellWeakLink> {
+        [&self.north, &self.south, &self.east, &self.west,
+         &self.northeast, &self.northwest, &self.southeast, &self.southwest]
+            .into_iter()
+            .flatten()
+            .map(Weak::clone)
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn upsilon_cell_octagon() {
+        assert!(UpsilonCell::new(2, 4).is_octagon());
+        assert!(!UpsilonCell::new(2, 3).is_octagon());
+    }
+}
@@ -0,0 +1,125 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::upsilon_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of an upsilon maze, tiled with octagons and squares alternating like the fields
+ * of a chessboard. The octagons are adjacent to each other diagonally.
+ */
+pub struct UpsilonGrid {
+    pub width: u16,
+    pub height: u16,
+
+    pub cells: Vec<Vec<Option<upsilon_cell::UpsilonCellStrongLink>>>,
+}
+
+impl UpsilonGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for row in 0..self.height as usize {
+            self.cells.push(vec![]);
+            for col in 0..self.width as usize {
+                self.cells[row].push(Some(Rc::new(RefCell::new(
+                    upsilon_cell::UpsilonCell::new(row as u16, col as u16)))));
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for cell in self.cells.iter().flatten().flatten() {
+            let mut c = RefCell::borrow_mut(cell);
+            let row_ = c.row as i32;
+            let col_ = c.col as i32;
+
+            c.north = self.create_neighbour(row_ - 1, col_);
+            c.south = self.create_neighbour(row_ + 1, col_);
+            c.east = self.create_neighbour(row_, col_ + 1);
+            c.west = self.create_neighbour(row_, col_ - 1);
+
+            if c.is_octagon() {
+                c.northeast = self.create_neighbour(row_ - 1, col_ + 1);
+                c.northwest = self.create_neighbour(row_ - 1, col_ - 1);
+                c.southeast = self.create_neighbour(row_ + 1, col_ + 1);
+                c.southwest = self.create_neighbour(row_ + 1, col_ - 1);
+            }
+        }
+    }
+
+
+    fn create_neighbour(&self, row: i32, col: i32) -> Option<upsilon_cell::UpsilonCellWeakLink> {
+        if 0 <= row && row < self.height as i32 && 0 <= col && col < self.width as i32 {
+            return Some(Rc::downgrade(self.cells[row as usize][col as usize].as_ref().unwrap()));
+        };
+        None
+    }
+}
+
+
+impl MazeGrid for UpsilonGrid {
+    type Cell = upsilon_cell::UpsilonCell;
+
+
+    fn each_cell(&self) -> Vec<upsilon_cell::UpsilonCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> upsilon_cell::UpsilonCellWeakLink {
+        let row_ = sample::gen_index(rng, self.height as usize);
+        let col_ = sample::gen_index(rng, self.width as usize);
+
+        Rc::downgrade(self.cells[row_][col_].as_ref().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maze::topology::MazeCell;
+
+
+    #[test]
+    fn upsilon_grid_neighbour_counts() {
+        let mut grid = UpsilonGrid::new(5, 5);
+        grid.init_grid();
+
+        let octagon = RefCell::borrow(grid.cells[2][2].as_ref().unwrap());
+        let square = RefCell::borrow(grid.cells[2][3].as_ref().unwrap());
+
+        assert_eq!(8, octagon.neighbours().len());
+        assert_eq!(4, square.neighbours().len());
+    }
+
+
+    #[test]
+    fn upsilon_grid_diagonals_are_octagons() {
+        let mut grid = UpsilonGrid::new(5, 5);
+        grid.init_grid();
+
+        let octagon = RefCell::borrow(grid.cells[1][1].as_ref().unwrap());
+        let northeast = octagon.northeast.as_ref().unwrap().upgrade().unwrap();
+
+        assert!(RefCell::borrow(&northeast).is_octagon());
+    }
+}
@@ -0,0 +1,62 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type VoronoiCellWeakLink = Weak<RefCell<VoronoiCell>>;
+pub type VoronoiCellStrongLink = Rc<RefCell<VoronoiCell>>;
+
+
+/*
+ * The VoronoiCell class used to represent a cell in the irregular maze. It is the region of
+ * the plane closer to its site than to any other one, and it is adjacent to the cells it shares
+ * an edge with, however many of them there are.
+ */
+pub struct VoronoiCell {
+    pub index: u32,
+    pub site: (f64, f64),
+
+    links: Vec<VoronoiCellWeakLink>,
+
+    pub polygon: Vec<(f64, f64)>,
+    pub adjacent: Vec<VoronoiCellWeakLink>,
+}
+
+impl PartialEq for VoronoiCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+
+impl VoronoiCell {
+    pub fn new(index: u32, site: (f64, f64)) -> Self {
+        Self {
+            index,
+            site,
+
+            links: vec![],
+
+            polygon: vec![],
+            adjacent: vec![],
+        }
+    }
+}
+
+
+impl MazeCell for VoronoiCell {
+    fn link_list(&self) -> &Vec<VoronoiCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<VoronoiCellWeakLink> {
+        &mut self.links
+    }
+
+
+    fn neighbours(&self) -> Vec<VoronoiCellWeakLink> {
+        self.adjacent.iter().map(Weak::clone).collect()
+    }
+}
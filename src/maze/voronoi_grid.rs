@@ -0,0 +1,360 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::voronoi_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+type Point = (f64, f64);
+
+
+// the tolerance of the geometric comparisons, relative to the size of a cell
+const EPSILON: f64 = 1e-9;
+
+
+/*
+ * The grid of an irregular maze, whose cells are the Voronoi regions of a set of sites within
+ * the 'width x height' rectangle. The regions are clipped by the rectangle, and the cells
+ * sharing an edge of their regions are adjacent, as found by the Delaunay triangulation.
+ * The sizes are in the units of the cells of a rectangular maze.
+ */
+pub struct VoronoiGrid {
+    pub width: u16,
+    pub height: u16,
+
+    sites: Vec<Point>,
+
+    pub cells: Vec<voronoi_cell::VoronoiCellStrongLink>,
+}
+
+impl VoronoiGrid {
+    /*
+     * Creates the grid over the specified sites, which shall be distinct and lie within
+     * the rectangle.
+     */
+    pub fn new(width: u16, height: u16, sites: Vec<Point>) -> Self {
+        Self {
+            width,
+            height,
+
+            sites,
+
+            cells: vec![],
+        }
+    }
+
+
+    /*
+     * Creates the grid over 'count' uniformly distributed random sites.
+     */
+    pub fn random<R: Rng + ?Sized>(width: u16, height: u16, count: usize, rng: &mut R) -> Self {
+        let sites = (0..count)
+            .map(|_| (sample::gen_unit(rng) * width as f64, sample::gen_unit(rng) * height as f64))
+            .collect();
+
+        Self::new(width, height, sites)
+    }
+
+
+    /*
+     * Creates the grid over random sites, which are at least a cell apart from each other,
+     * but evenly fill the rectangle, using the Bridson's Poisson-disc sampling. Hence the cells
+     * are more regular than the ones over uniformly distributed sites.
+     */
+    pub fn poisson<R: Rng + ?Sized>(width: u16, height: u16, rng: &mut R) -> Self {
+        const ATTEMPTS: usize = 30;
+        let (w, h) = (width as f64, height as f64);
+
+        // every bucket holds a single site at most, as it is narrower than the distance
+        let bucket = 1.0 / 2f64.sqrt();
+        let (cols, rows) = ((w / bucket).ceil() as usize, (h / bucket).ceil() as usize);
+        let mut buckets: Vec<Option<usize>> = vec![None; cols * rows];
+        let bucket_of = |(x, y): Point| ((y / bucket) as usize, (x / bucket) as usize);
+
+        let mut sites = vec![(sample::gen_unit(rng) * w, sample::gen_unit(rng) * h)];
+        let (r, c) = bucket_of(sites[0]);
+        buckets[r * cols + c] = Some(0);
+        let mut active = vec![0];
+
+        while !active.is_empty() {
+            let i = sample::gen_index(rng, active.len());
+            let site = sites[active[i]];
+
+            let mut found = false;
+            for _ in 0..ATTEMPTS {
+                // tries a random point within the ring from 1 to 2 cells around the site
+                let angle = 2.0 * PI * sample::gen_unit(rng);
+                let distance = 1.0 + sample::gen_unit(rng);
+                let p = (site.0 + distance * angle.cos(), site.1 + distance * angle.sin());
+                if !(0.0..w).contains(&p.0) || !(0.0..h).contains(&p.1) {
+                    continue;
+                }
+
+                let (r, c) = bucket_of(p);
+                let is_far = (r.saturating_sub(2)..(r + 3).min(rows))
+                    .flat_map(|r| (c.saturating_sub(2)..(c + 3).min(cols)).map(move |c| (r, c)))
+                    .filter_map(|(r, c)| buckets[r * cols + c])
+                    .all(|other| distance_sq(p, sites[other]) >= 1.0);
+
+                if is_far {
+                    buckets[r * cols + c] = Some(sites.len());
+                    active.push(sites.len());
+                    sites.push(p);
+                    found = true;
+                    break;
+                }
+            }
+
+            if !found {
+                active.swap_remove(i);
+            }
+        }
+
+        Self::new(width, height, sites)
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        self.cells = self.sites.iter()
+            .enumerate()
+            .map(|(i, &site)| Rc::new(RefCell::new(voronoi_cell::VoronoiCell::new(i as u32, site))))
+            .collect();
+    }
+
+
+    fn configure_cells(&mut self) {
+        let (w, h) = (self.width as f64, self.height as f64);
+        let triangulation = delaunay(&self.sites);
+
+        // the region of each cell lies on the inner side of the bisectors with all of its
+        // Delaunay neighbours
+        let mut candidates = vec![vec![]; self.sites.len()];
+        for &(a, b) in &triangulation {
+            candidates[a].push(b);
+            candidates[b].push(a);
+        }
+
+        for (i, cell) in self.cells.iter().enumerate() {
+            let mut polygon = vec![(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+            for &j in &candidates[i] {
+                polygon = clip(&polygon, self.sites[i], self.sites[j]);
+            }
+            RefCell::borrow_mut(cell).polygon = polygon;
+        }
+
+        // only the neighbours sharing an edge within the rectangle are adjacent, while
+        // the others only touch each other outside of it
+        for &(a, b) in &triangulation {
+            if shared_edge(&RefCell::borrow(&self.cells[a]).polygon, self.sites[a], self.sites[b]).is_some() {
+                RefCell::borrow_mut(&self.cells[a]).adjacent.push(Rc::downgrade(&self.cells[b]));
+                RefCell::borrow_mut(&self.cells[b]).adjacent.push(Rc::downgrade(&self.cells[a]));
+            }
+        }
+    }
+}
+
+
+/*
+ * Returns the edge of the polygon around the 'site', which lies on the bisector with
+ * the 'other' site, if there is any.
+ */
+pub fn shared_edge(polygon: &[Point], site: Point, other: Point) -> Option<(Point, Point)> {
+    let scale = distance_sq(site, other).sqrt();
+    let on_bisector = |p: Point| (distance_sq(p, site).sqrt() - distance_sq(p, other).sqrt()).abs()
+        < EPSILON.sqrt() * scale.max(1.0);
+
+    (0..polygon.len())
+        .map(|k| (polygon[k], polygon[(k + 1) % polygon.len()]))
+        .find(|&(a, b)| on_bisector(a) && on_bisector(b) && distance_sq(a, b) > EPSILON)
+}
+
+
+fn distance_sq(a: Point, b: Point) -> f64 {
+    (a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)
+}
+
+
+// clips the convex polygon by the half-plane closer to 'site' than to 'other'
+fn clip(polygon: &[Point], site: Point, other: Point) -> Vec<Point> {
+    let normal = (other.0 - site.0, other.1 - site.1);
+    let middle = ((site.0 + other.0) / 2.0, (site.1 + other.1) / 2.0);
+    let side = |p: Point| (p.0 - middle.0) * normal.0 + (p.1 - middle.1) * normal.1;
+
+    let mut clipped = vec![];
+    for k in 0..polygon.len() {
+        let (a, b) = (polygon[k], polygon[(k + 1) % polygon.len()]);
+        let (sa, sb) = (side(a), side(b));
+
+        if sa <= 0.0 {
+            clipped.push(a);
+        }
+        if sa < 0.0 && sb > 0.0 || sa > 0.0 && sb < 0.0 {
+            let t = sa / (sa - sb);
+            clipped.push((a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1)));
+        }
+    }
+    clipped
+}
+
+
+// returns the edges of the Delaunay triangulation of the sites, found using
+// the Bowyer-Watson algorithm
+fn delaunay(sites: &[Point]) -> Vec<(usize, usize)> {
+    let n = sites.len();
+    if n < 2 {
+        return vec![];
+    }
+
+    // a triangle enclosing all sites far away, whose corners are removed at the end
+    let (min_x, max_x) = sites.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+    let (min_y, max_y) = sites.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+    let span = (max_x - min_x).max(max_y - min_y).max(1.0) * 100.0;
+    let (cx, cy) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+    let mut points = sites.to_vec();
+    points.extend([(cx - 2.0 * span, cy - span), (cx + 2.0 * span, cy - span), (cx, cy + 2.0 * span)]);
+
+    let mut triangles = vec![Triangle::new(&points, [n, n + 1, n + 2])];
+
+    for (i, &p) in sites.iter().enumerate() {
+        let (bad, good): (Vec<Triangle>, Vec<Triangle>) = triangles.into_iter()
+            .partition(|t| distance_sq(t.centre, p) < t.radius_sq);
+
+        // the edges of the hole left by the removed triangles, which are not shared by two of them
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for t in &bad {
+            for (a, b) in t.edges() {
+                if let Some(k) = edges.iter().position(|&(x, y)| (x, y) == (b, a) || (x, y) == (a, b)) {
+                    edges.swap_remove(k);
+                } else {
+                    edges.push((a, b));
+                }
+            }
+        }
+
+        triangles = good;
+        triangles.extend(edges.into_iter().map(|(a, b)| Triangle::new(&points, [a, b, i])));
+    }
+
+    let mut result = HashSet::new();
+    for t in triangles.iter().filter(|t| t.vertices.iter().all(|&v| v < n)) {
+        for (a, b) in t.edges() {
+            result.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut result: Vec<(usize, usize)> = result.into_iter().collect();
+    result.sort_unstable();
+    result
+}
+
+
+// a triangle of the Delaunay triangulation, along with its circumcircle
+struct Triangle {
+    vertices: [usize; 3],
+    centre: Point,
+    radius_sq: f64,
+}
+
+impl Triangle {
+    fn new(points: &[Point], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|v| points[v]);
+        let d = 2.0 * (a.0 * (b.1 - c.1) + b.0 * (c.1 - a.1) + c.0 * (a.1 - b.1));
+
+        // the sites on a line have no circumcircle, hence no other site falls into it
+        if d.abs() < EPSILON {
+            return Self { vertices, centre: a, radius_sq: f64::NEG_INFINITY };
+        }
+
+        let (a2, b2, c2) = (a.0 * a.0 + a.1 * a.1, b.0 * b.0 + b.1 * b.1, c.0 * c.0 + c.1 * c.1);
+        let centre = ((a2 * (b.1 - c.1) + b2 * (c.1 - a.1) + c2 * (a.1 - b.1)) / d,
+                      (a2 * (c.0 - b.0) + b2 * (a.0 - c.0) + c2 * (b.0 - a.0)) / d);
+
+        Self { vertices, centre, radius_sq: distance_sq(centre, a) }
+    }
+
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+
+impl MazeGrid for VoronoiGrid {
+    type Cell = voronoi_cell::VoronoiCell;
+
+
+    fn each_cell(&self) -> Vec<voronoi_cell::VoronoiCellStrongLink> {
+        self.cells.iter().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> voronoi_cell::VoronoiCellWeakLink {
+        Rc::downgrade(sample::choose(rng, &self.cells).unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::algos::recursive_backtracking;
+    use crate::maze::topology::MazeCell;
+
+
+    // computes the area of the polygon using the shoelace formula
+    fn area(polygon: &[Point]) -> f64 {
+        (0..polygon.len())
+            .map(|k| {
+                let (a, b) = (polygon[k], polygon[(k + 1) % polygon.len()]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum::<f64>()
+            .abs() / 2.0
+    }
+
+
+    #[test]
+    fn voronoi_grid_poisson_sites_apart() {
+        let grid = VoronoiGrid::poisson(12, 8, &mut StdRng::seed_from_u64(7));
+
+        assert!(grid.sites.len() > 12 * 8 / 2);
+        for (i, &a) in grid.sites.iter().enumerate() {
+            assert!(grid.sites[i + 1..].iter().all(|&b| distance_sq(a, b) >= 1.0));
+        }
+    }
+
+
+    #[test]
+    fn voronoi_grid_regions_tile_rectangle() {
+        let mut grid = VoronoiGrid::random(10, 6, 80, &mut StdRng::seed_from_u64(7));
+        grid.init_grid();
+
+        let total: f64 = grid.cells.iter().map(|c| area(&RefCell::borrow(c).polygon)).sum();
+
+        assert!((total - 60.0).abs() < 1e-6);
+    }
+
+
+    #[test]
+    fn voronoi_grid_perfect_maze() {
+        let mut grid = VoronoiGrid::poisson(10, 10, &mut StdRng::seed_from_u64(3));
+        grid.init_grid();
+
+        recursive_backtracking::build_maze(&grid, &mut StdRng::seed_from_u64(3));
+
+        let links: usize = grid.cells.iter().map(|c| RefCell::borrow(c).links().len()).sum();
+        assert_eq!(2 * (grid.cells.len() - 1), links);
+    }
+}
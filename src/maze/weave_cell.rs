@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::cmp::PartialEq;
+use crate::maze::topology::MazeCell;
+
+
+pub type WeaveCellWeakLink = Weak<RefCell<WeaveCell>>;
+pub type WeaveCellStrongLink = Rc<RefCell<WeaveCell>>;
+
+
+// the ways to reach the adjacent cell in each direction, north, south, east and west
+const DIRECTIONS: [fn(&WeaveCell) -> &Option<WeaveCellWeakLink>; 4] =
+    [|c| &c.north, |c| &c.south, |c| &c.east, |c| &c.west];
+
+
+/*
+ * The WeaveCell class used to represent a cell in the weave maze. Besides the cells laid
+ * on the grid, there are cells tunnelling beneath them, which are owned by the cell above
+ * and share its position. A passage from a cell to the one two steps away in a straight
+ * line passes through such an under cell, if the cell in between is a straight corridor
+ * perpendicular to the passage.
+ */
+pub struct WeaveCell {
+    pub row: u16,
+    pub col: u16,
+
+    links: Vec<WeaveCellWeakLink>,
+
+    pub north: Option<WeaveCellWeakLink>,
+    pub south: Option<WeaveCellWeakLink>,
+    pub east: Option<WeaveCellWeakLink>,
+    pub west: Option<WeaveCellWeakLink>,
+
+    // the cell tunnelling beneath this one, if there is any
+    pub under: Option<WeaveCellStrongLink>,
+    is_under: bool,
+}
+
+impl PartialEq for WeaveCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.row == other.row && self.col == other.col && self.is_under == other.is_under
+    }
+}
+
+
+impl WeaveCell {
+    pub fn new(row: u16, col: u16) -> Self {
+        Self {
+            row,
+            col,
+
+            links: vec![],
+
+            north: None,
+            south: None,
+            east: None,
+            west: None,
+
+            under: None,
+            is_under: false,
+        }
+    }
+
+
+    /*
+     * Checks if the cell tunnels beneath another one.
+     */
+    pub fn is_under(&self) -> bool {
+        self.is_under
+    }
+
+
+    /*
+     * Checks if the tunnel of an under cell leads from north to south, otherwise it leads
+     * from east to west. Cells laid on the grid are not tunnels.
+     */
+    pub fn is_vertical_tunnel(&self) -> bool {
+        self.is_under && self.north.is_some()
+    }
+
+
+    /*
+     * Checks if there is a passage leaving the cell through the side facing the specified
+     * adjacent cell, be it to that cell or to the cell tunnelling beneath it.
+     */
+    pub fn is_open_towards(&self, cell: &WeaveCellWeakLink) -> bool {
+        let cell = cell.upgrade().unwrap();
+        let (row, col) = (RefCell::borrow(&cell).row, RefCell::borrow(&cell).col);
+
+        self.links.iter().any(|link| {
+            let link = link.upgrade().unwrap();
+            let l = RefCell::borrow(&link);
+            l.row == row && l.col == col
+        })
+    }
+
+
+    // checks if the cell is a straight corridor, leading either from east to west,
+    // or from north to south, which a tunnel can pass beneath
+    fn is_straight_corridor(&self, horizontal: bool) -> bool {
+        let (a, b) = if horizontal { (&self.east, &self.west) } else { (&self.north, &self.south) };
+
+        match (a, b) {
+            (Some(a), Some(b)) => !self.is_under && self.under.is_none() && self.links.len() == 2
+                && self.is_linked_to(Weak::clone(a)) && self.is_linked_to(Weak::clone(b)),
+            _ => false,
+        }
+    }
+
+
+    // returns the cell in between, if the specified cell lies two steps away in a straight
+    // line, along with the direction of the step
+    fn hop_over(&self, cell: &WeaveCellStrongLink) -> Option<(WeaveCellStrongLink, usize)> {
+        for (d, direction) in DIRECTIONS.iter().enumerate() {
+            let Some(middle) = direction(self).as_ref().map(|m| m.upgrade().unwrap()) else {
+                continue;
+            };
+            let beyond = direction(&RefCell::borrow(&middle)).as_ref().map(|b| b.upgrade().unwrap());
+            if beyond.is_some_and(|b| *RefCell::borrow(&b) == *RefCell::borrow(cell)) {
+                return Some((middle, d));
+            }
+        }
+        None
+    }
+}
+
+
+impl MazeCell for WeaveCell {
+    fn link_list(&self) -> &Vec<WeaveCellWeakLink> {
+        &self.links
+    }
+
+
+    fn link_list_mut(&mut self) -> &mut Vec<WeaveCellWeakLink> {
+        &mut self.links
+    }
+
+
+    /*
+     * Returns a list of all adjacent neighbours, and of the cells a tunnel could lead to
+     * from a cell laid on the grid.
+     */
+    fn neighbours(&self) -> Vec<WeaveCellWeakLink> {
+        let mut lst: Vec<WeaveCellWeakLink> = DIRECTIONS.iter()
+            .filter_map(|direction| direction(self).as_ref().map(Weak::clone))
+            .collect();
+
+        if !self.is_under {
+            for direction in DIRECTIONS {
+                let Some(middle) = direction(self).as_ref().map(|m| m.upgrade().unwrap()) else {
+                    continue;
+                };
+                let beyond = direction(&RefCell::borrow(&middle)).as_ref().map(Weak::clone);
+                lst.extend(beyond);
+            }
+        }
+
+        lst
+    }
+
+
+    /*
+     * Checks if a passage to the specified cell can be created. The cells with a tunnel
+     * beneath them already have all their passages, and a tunnel may only pass beneath
+     * a straight corridor perpendicular to it.
+     */
+    fn can_link_to(&self, cell: WeaveCellWeakLink) -> bool {
+        let cell = cell.upgrade().unwrap();
+        if self.is_under || self.under.is_some() || RefCell::borrow(&cell).under.is_some() {
+            return false;
+        }
+
+        let is_adjacent = DIRECTIONS.iter().any(|direction| direction(self).as_ref()
+            .is_some_and(|n| *n.upgrade().unwrap().borrow() == *RefCell::borrow(&cell)));
+        if is_adjacent {
+            return true;
+        }
+
+        match self.hop_over(&cell) {
+            // the steps north and south are the first two directions
+            Some((middle, d)) => RefCell::borrow(&middle).is_straight_corridor(d < 2),
+            None => false,
+        }
+    }
+
+
+    /*
+     * Creates a link to the specified cell, which is either adjacent, or two steps away,
+     * in which case the link leads to the cell tunnelling beneath the one in between.
+     */
+    fn link_to(&mut self, cell: WeaveCellWeakLink) {
+        let cell = cell.upgrade().unwrap();
+
+        let Some((middle, d)) = self.hop_over(&cell) else {
+            self.links.push(Rc::downgrade(&cell));
+            return;
+        };
+
+        let mut m = RefCell::borrow_mut(&middle);
+        if m.under.is_none() {
+            let mut under = WeaveCell::new(m.row, m.col);
+            under.is_under = true;
+            if d < 2 {
+                under.north = m.north.as_ref().map(Weak::clone);
+                under.south = m.south.as_ref().map(Weak::clone);
+            } else {
+                under.east = m.east.as_ref().map(Weak::clone);
+                under.west = m.west.as_ref().map(Weak::clone);
+            }
+            under.links = under.neighbours();
+
+            m.under = Some(Rc::new(RefCell::new(under)));
+        }
+
+        self.links.push(Rc::downgrade(m.under.as_ref().unwrap()));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    // creates a row of cells, linked to their neighbours on both sides
+    fn row_of(cells: &[WeaveCellStrongLink]) {
+        for pair in cells.windows(2) {
+            RefCell::borrow_mut(&pair[0]).east = Some(Rc::downgrade(&pair[1]));
+            RefCell::borrow_mut(&pair[1]).west = Some(Rc::downgrade(&pair[0]));
+        }
+    }
+
+
+    #[test]
+    fn weave_cell_tunnels_beneath_corridor() {
+        let cells: Vec<WeaveCellStrongLink> = [(1, 0), (1, 1), (1, 2), (0, 1), (2, 1)].iter()
+            .map(|&(r, c)| Rc::new(RefCell::new(WeaveCell::new(r, c))))
+            .collect();
+        let (west, centre, east, north, south) = (&cells[0], &cells[1], &cells[2], &cells[3], &cells[4]);
+        row_of(&[Rc::clone(west), Rc::clone(centre), Rc::clone(east)]);
+        RefCell::borrow_mut(centre).north = Some(Rc::downgrade(north));
+        RefCell::borrow_mut(centre).south = Some(Rc::downgrade(south));
+        RefCell::borrow_mut(north).south = Some(Rc::downgrade(centre));
+        RefCell::borrow_mut(south).north = Some(Rc::downgrade(centre));
+
+        assert!(!RefCell::borrow(north).can_link_to(Rc::downgrade(south)));
+
+        for (a, b) in [(west, centre), (centre, east)] {
+            RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+            RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+        }
+        assert!(RefCell::borrow(north).can_link_to(Rc::downgrade(south)));
+
+        RefCell::borrow_mut(north).link_to(Rc::downgrade(south));
+        RefCell::borrow_mut(south).link_to(Rc::downgrade(north));
+
+        let under = Rc::clone(RefCell::borrow(centre).under.as_ref().unwrap());
+        assert!(RefCell::borrow(&under).is_vertical_tunnel());
+        assert!(RefCell::borrow(north).is_linked_to(Rc::downgrade(&under)));
+        assert!(RefCell::borrow(south).is_linked_to(Rc::downgrade(&under)));
+        assert!(!RefCell::borrow(north).is_linked_to(Rc::downgrade(centre)));
+        assert!(!RefCell::borrow(north).can_link_to(Rc::downgrade(centre)));
+    }
+}
@@ -0,0 +1,167 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use rand::Rng;
+use crate::maze::weave_cell;
+use crate::maze::topology::MazeGrid;
+use crate::rng::sample;
+
+
+/*
+ * The grid of a weave maze, whose passages may tunnel beneath the perpendicular ones.
+ * Only the cells laid on the grid are stored here, the cells tunnelling beneath them are
+ * created by the generators and owned by the cells above.
+ */
+pub struct WeaveGrid {
+    pub width: u16,
+    pub height: u16,
+
+    pub cells: Vec<Vec<Option<weave_cell::WeaveCellStrongLink>>>,
+}
+
+impl WeaveGrid {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![],
+        }
+    }
+
+
+    pub fn init_grid(&mut self) {
+        self.prepare_grid();
+        self.configure_cells();
+    }
+
+
+    fn prepare_grid(&mut self) {
+        for row in 0..self.height as usize {
+            self.cells.push(vec![]);
+            for col in 0..self.width as usize {
+                self.cells[row].push(Some(Rc::new(RefCell::new(
+                    weave_cell::WeaveCell::new(row as u16, col as u16)))));
+            }
+        }
+    }
+
+
+    fn configure_cells(&mut self) {
+        for cell in self.cells.iter().flatten().flatten() {
+            let mut c = RefCell::borrow_mut(cell);
+            let row_ = c.row as i32;
+            let col_ = c.col as i32;
+
+            c.north = self.create_neighbour(row_ - 1, col_);
+            c.south = self.create_neighbour(row_ + 1, col_);
+            c.east = self.create_neighbour(row_, col_ + 1);
+            c.west = self.create_neighbour(row_, col_ - 1);
+        }
+    }
+
+
+    fn create_neighbour(&self, row: i32, col: i32) -> Option<weave_cell::WeaveCellWeakLink> {
+        if 0 <= row && row < self.height as i32 && 0 <= col && col < self.width as i32 {
+            return Some(Rc::downgrade(self.cells[row as usize][col as usize].as_ref().unwrap()));
+        };
+        None
+    }
+
+
+    /*
+     * Returns a list of all cells tunnelling beneath the cells of the grid.
+     */
+    pub fn under_cells(&self) -> Vec<weave_cell::WeaveCellStrongLink> {
+        self.cells.iter().flatten().flatten()
+            .filter_map(|cell| RefCell::borrow(cell).under.as_ref().map(Rc::clone))
+            .collect()
+    }
+}
+
+
+impl MazeGrid for WeaveGrid {
+    type Cell = weave_cell::WeaveCell;
+
+
+    fn each_cell(&self) -> Vec<weave_cell::WeaveCellStrongLink> {
+        self.cells.iter().flatten().flatten().map(Rc::clone).collect()
+    }
+
+
+    fn get_rand_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> weave_cell::WeaveCellWeakLink {
+        let row_ = sample::gen_index(rng, self.height as usize);
+        let col_ = sample::gen_index(rng, self.width as usize);
+
+        Rc::downgrade(self.cells[row_][col_].as_ref().unwrap())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use crate::algos::{kruskal, recursive_backtracking};
+    use crate::maze::topology::MazeCell;
+    use crate::maze::weave_cell::WeaveCellStrongLink;
+    use rand::{SeedableRng, rngs::StdRng};
+
+
+    // counts the cells reachable from the top left corner, walking also through the tunnels
+    fn count_reachable(grid: &WeaveGrid) -> usize {
+        let key = |c: &WeaveCellStrongLink| {
+            let c = RefCell::borrow(c);
+            (c.row, c.col, c.is_under())
+        };
+        let start = Rc::clone(grid.cells[0][0].as_ref().unwrap());
+        let mut visited = HashSet::from([key(&start)]);
+        let mut stack = vec![start];
+
+        while let Some(cell) = stack.pop() {
+            for link in RefCell::borrow(&cell).links() {
+                let link = link.upgrade().unwrap();
+                if visited.insert(key(&link)) {
+                    stack.push(link);
+                }
+            }
+        }
+        visited.len()
+    }
+
+
+    // checks the maze is perfect, i.e. it is a spanning tree over all cells, tunnels included
+    fn assert_perfect(grid: &WeaveGrid) {
+        let cells = grid.each_cell().len() + grid.under_cells().len();
+        let links: usize = grid.each_cell().iter().chain(grid.under_cells().iter())
+            .map(|c| RefCell::borrow(c).links().len())
+            .sum();
+
+        assert_eq!(2 * (cells - 1), links);
+        assert_eq!(cells, count_reachable(grid));
+    }
+
+
+    #[test]
+    fn weave_grid_backtracking() {
+        let mut grid = WeaveGrid::new(12, 12);
+        grid.init_grid();
+
+        recursive_backtracking::build_maze(&grid, &mut StdRng::seed_from_u64(3));
+
+        assert!(!grid.under_cells().is_empty());
+        assert_perfect(&grid);
+    }
+
+
+    #[test]
+    fn weave_grid_kruskal() {
+        let mut grid = WeaveGrid::new(12, 12);
+        grid.init_grid();
+
+        kruskal::build_maze(&grid, &mut StdRng::seed_from_u64(3));
+
+        // the tunnels only go beneath the corridors opened before, yet some of them do
+        assert!(!grid.under_cells().is_empty());
+        assert_perfect(&grid);
+    }
+}
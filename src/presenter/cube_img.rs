@@ -0,0 +1,78 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use crate::maze::cube_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the cube maze as its unfolded net, a cross of four faces side by side, with the top
+ * face above and the bottom face below the second one, ready to be cut out and folded.
+ * The passages across the edges cut apart on the net show as gaps meeting once folded.
+ */
+impl Drawable for cube_grid::CubeGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as u32;
+        let size = (img.cell_size() + img.wall_thickness()) as u32;
+        let face_size = self.size as u32 * size;
+
+        let width = 4 * face_size + thickness;
+        let height = 3 * face_size + thickness;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        for cell in self.cells.iter().flatten().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            let (net_row, net_col) = cube_grid::NET[c.face as usize];
+
+            let x1 = net_col as u32 * face_size + c.col as u32 * size;
+            let y1 = net_row as u32 * face_size + c.row as u32 * size;
+            let (x2, y2) = (x1 + size, y1 + size);
+
+            let is_wall = |side: &Option<Weak<_>>| match side {
+                Some(n) => !c.is_linked_to(Weak::clone(n)),
+                None => true,
+            };
+
+            // the walls shared by two cells are drawn by both of them
+            for (side, x, y, w, h) in [(&c.north, x1, y1, size + thickness, thickness),
+                                       (&c.south, x1, y2, size + thickness, thickness),
+                                       (&c.west, x1, y1, thickness, size + thickness),
+                                       (&c.east, x2, y1, thickness, size + thickness)] {
+                if is_wall(side) {
+                    draw_filled_rect_mut(&mut image, Rect::at(x as i32, y as i32).of_size(w, h), BLACK);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_cube() {
+        let mut grid = cube_grid::CubeGrid::new(6);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_cube.png");
+
+        let saved = image::open("test_maze_cube.png").unwrap();
+        assert_eq!((4 * 6 * 22 + 2, 3 * 6 * 22 + 2), (saved.width(), saved.height()));
+    }
+}
@@ -0,0 +1,142 @@
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_line_segment_mut, draw_polygon_mut};
+use imageproc::point::Point;
+use crate::maze::font;
+
+
+/*
+ * The drawing primitives shared by the presenters of the mazes, whose walls are not aligned
+ * with the axes of the image.
+ */
+
+
+/*
+ * Draws a line of the specified thickness with rounded ends, so the adjacent walls join
+ * smoothly at any angle.
+ */
+pub fn thick_line(image: &mut RgbImage, start: (f64, f64), end: (f64, f64), thickness: f64,
+                  colour: Rgb<u8>) {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt();
+
+    let corners = if length > 0.0 {
+        // the normal vector of the line, as long as half of its thickness
+        let (nx, ny) = (-dy / length * thickness / 2.0, dx / length * thickness / 2.0);
+        [(start.0 + nx, start.1 + ny), (end.0 + nx, end.1 + ny),
+         (end.0 - nx, end.1 - ny), (start.0 - nx, start.1 - ny)]
+            .map(|(x, y)| Point::new(x.round() as i32, y.round() as i32))
+            .to_vec()
+    } else {
+        vec![]
+    };
+
+    // thin or degenerate lines cannot be drawn as a polygon
+    if corners.is_empty() || corners[0] == corners[3] || corners[0] == corners[1] {
+        draw_line_segment_mut(image, (start.0 as f32, start.1 as f32),
+                              (end.0 as f32, end.1 as f32), colour);
+    } else {
+        draw_polygon_mut(image, &corners, colour);
+    }
+
+    let radius = (thickness / 2.0).floor() as i32;
+    if radius > 0 {
+        for (x, y) in [start, end] {
+            draw_filled_circle_mut(image, (x.round() as i32, y.round() as i32), radius, colour);
+        }
+    }
+}
+
+
+/*
+ * Draws a polyline of the specified thickness through all points.
+ */
+pub fn thick_polyline(image: &mut RgbImage, points: &[(f64, f64)], thickness: f64,
+                      colour: Rgb<u8>) {
+    for pair in points.windows(2) {
+        thick_line(image, pair[0], pair[1], thickness, colour);
+    }
+}
+
+
+/*
+ * Draws an arc of the circle around the centre, going clockwise from the start angle
+ * to the end angle, which are specified in radians.
+ */
+pub fn arc(image: &mut RgbImage, centre: (f64, f64), radius: f64, start: f64, end: f64,
+           thickness: f64, colour: Rgb<u8>) {
+    // splits the arc into segments a few pixels long, which look smooth enough
+    let steps = ((end - start).abs() * radius / 4.0).ceil().max(1.0) as usize;
+
+    let points: Vec<(f64, f64)> = (0..=steps)
+        .map(|i| start + (end - start) * i as f64 / steps as f64)
+        .map(|angle| (centre.0 + radius * angle.cos(), centre.1 + radius * angle.sin()))
+        .collect();
+
+    thick_polyline(image, &points, thickness, colour);
+}
+
+
+/*
+ * Writes the text centred on the point using the built-in bitmap font, whose pixels are
+ * drawn as squares of the specified size. The pixels falling outside the image are skipped.
+ */
+pub fn text(image: &mut RgbImage, centre: (f64, f64), text: &str, pixel: u32, colour: Rgb<u8>) {
+    let advance = font::GLYPH_WIDTH + font::SPACING;
+    let width = (text.chars().count() * advance).saturating_sub(font::SPACING) as f64 * pixel as f64;
+    let height = font::GLYPH_HEIGHT as f64 * pixel as f64;
+    let left = (centre.0 - width / 2.0).round() as i64;
+    let top = (centre.1 - height / 2.0).round() as i64;
+
+    for (index, c) in text.chars().enumerate() {
+        let glyph = font::glyph(c);
+        for row in 0..font::GLYPH_HEIGHT {
+            for col in (0..font::GLYPH_WIDTH).filter(|&col| font::is_set(&glyph, row, col)) {
+                let x = left + ((index * advance + col) as u32 * pixel) as i64;
+                let y = top + (row as u32 * pixel) as i64;
+                for (px, py) in (0..pixel as i64).flat_map(|dx| (0..pixel as i64).map(move |dy| (x + dx, y + dy))) {
+                    if (0..image.width() as i64).contains(&px) && (0..image.height() as i64).contains(&py) {
+                        image.put_pixel(px as u32, py as u32, colour);
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::ImageBuffer;
+
+
+    #[test]
+    fn draw_thick_line() {
+        let mut image: RgbImage = ImageBuffer::new(20, 20);
+        thick_line(&mut image, (2.0, 2.0), (17.0, 17.0), 4.0, Rgb([255, 0, 0]));
+
+        assert_eq!(Rgb([255, 0, 0]), *image.get_pixel(10, 10));
+        assert_eq!(Rgb([0, 0, 0]), *image.get_pixel(17, 2));
+    }
+
+
+    #[test]
+    fn draw_text() {
+        let mut image: RgbImage = ImageBuffer::new(20, 12);
+        text(&mut image, (10.0, 6.0), "1", 1, Rgb([255, 0, 0]));
+
+        // the foot of the '1' is three pixels wide below its stem
+        assert_eq!(Rgb([255, 0, 0]), *image.get_pixel(10, 5));
+        assert_eq!(Rgb([255, 0, 0]), *image.get_pixel(9, 9));
+        assert_eq!(Rgb([0, 0, 0]), *image.get_pixel(8, 5));
+    }
+
+
+    #[test]
+    fn draw_degenerate_line() {
+        let mut image: RgbImage = ImageBuffer::new(5, 5);
+        thick_line(&mut image, (2.0, 2.0), (2.0, 2.0), 1.0, Rgb([255, 0, 0]));
+
+        assert_eq!(Rgb([255, 0, 0]), *image.get_pixel(2, 2));
+    }
+}
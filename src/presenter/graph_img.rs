@@ -0,0 +1,83 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_filled_rect_mut};
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use crate::maze::graph_grid;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the maze over a graph as the tree of its passages, as the graph has no walls to draw.
+ * The positions of the nodes are in the units of the cells of a rectangular maze, and
+ * the nodes without a position are left out, along with their passages.
+ */
+impl Drawable for graph_grid::GraphGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+
+        let positions: Vec<(f64, f64)> = self.cells.iter()
+            .filter_map(|c| RefCell::borrow(c).position)
+            .collect();
+        let (min_x, max_x) = positions.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.0), hi.max(p.0)));
+        let (min_y, max_y) = positions.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.1), hi.max(p.1)));
+
+        // keeps half of a cell around the outermost nodes
+        let (width, height) = if positions.is_empty() {
+            (size as u32, size as u32)
+        } else {
+            (((max_x - min_x + 1.0) * size).ceil() as u32, ((max_y - min_y + 1.0) * size).ceil() as u32)
+        };
+        let to_px = |(x, y): (f64, f64)| ((x - min_x + 0.5) * size, (y - min_y + 0.5) * size);
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        for (a, b) in self.passages() {
+            let (a, b) = (RefCell::borrow(&self.cells[a]).position, RefCell::borrow(&self.cells[b]).position);
+            if let (Some(a), Some(b)) = (a, b) {
+                draw::thick_line(&mut image, to_px(a), to_px(b), thickness, BLACK);
+            }
+        }
+
+        let radius = (thickness as i32).max(1) + 1;
+        for (x, y) in positions.into_iter().map(to_px) {
+            draw_filled_circle_mut(&mut image, (x.round() as i32, y.round() as i32), radius, BLACK);
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::kruskal::build_maze;
+
+
+    #[test]
+    fn draw_graph() {
+        // a grid of 5 x 4 nodes with the diagonals of every other square
+        let position = |i: usize| Some(((i % 5) as f64, (i / 5) as f64));
+        let mut edges = vec![];
+        for i in 0..20 {
+            if i % 5 < 4 { edges.push((i, i + 1)); }
+            if i < 15 { edges.push((i, i + 5)); }
+            if i % 5 < 4 && i < 15 && i % 2 == 0 { edges.push((i, i + 6)); }
+        }
+        let mut grid = graph_grid::GraphGrid::new((0..20).map(position).collect(), edges);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_graph.png");
+
+        let saved = image::open("test_maze_graph.png").unwrap();
+        assert_eq!((5 * 22, 4 * 22), (saved.width(), saved.height()));
+    }
+}
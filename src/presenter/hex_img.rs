@@ -0,0 +1,100 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::maze::hex_cell::HexCellWeakLink;
+use crate::maze::hex_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the hex maze, where each hexagon is as high, as a cell in the rectangular maze
+ * is wide. Every wall shared by two cells is drawn only once, by the cell on its west,
+ * or on its north, while the border walls are drawn by the only cell they belong to.
+ */
+impl Drawable for hex_grid::HexGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+
+        // the half of the height of a hexagon, its side, and the half of its side
+        let b_size = (img.cell_size() + img.wall_thickness()) as f64 / 2.0;
+        let size = b_size * 2.0 / 3f64.sqrt();
+        let a_size = size / 2.0;
+
+        let width = (3.0 * a_size * self.width as f64 + a_size + 2.0 * thickness).ceil() as u32;
+        let height = (2.0 * b_size * self.height as f64 + b_size + 2.0 * thickness).ceil() as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+
+            let cx = thickness + size + 3.0 * c.col as f64 * a_size;
+            let cy = thickness + b_size + 2.0 * c.row as f64 * b_size
+                + if c.col % 2 == 1 { b_size } else { 0.0 };
+
+            // the x coordinates of the far west, near west, near east and far east vertices
+            let (x_fw, x_nw, x_ne, x_fe) = (cx - size, cx - a_size, cx + a_size, cx + size);
+            // the y coordinates of the north, middle and south vertices
+            let (y_n, y_m, y_s) = (cy - b_size, cy, cy + b_size);
+
+            let is_open = |neighbour: &Option<HexCellWeakLink>| match neighbour {
+                Some(n) => c.is_linked_to(Rc::downgrade(&n.upgrade().unwrap())),
+                None => false,
+            };
+
+            let mut walls = vec![];
+            if c.southwest.is_none() {
+                walls.push(((x_fw, y_m), (x_nw, y_s)));
+            }
+            if c.northwest.is_none() {
+                walls.push(((x_fw, y_m), (x_nw, y_n)));
+            }
+            if c.north.is_none() {
+                walls.push(((x_nw, y_n), (x_ne, y_n)));
+            }
+            if !is_open(&c.northeast) {
+                walls.push(((x_ne, y_n), (x_fe, y_m)));
+            }
+            if !is_open(&c.southeast) {
+                walls.push(((x_fe, y_m), (x_ne, y_s)));
+            }
+            if !is_open(&c.south) {
+                walls.push(((x_ne, y_s), (x_nw, y_s)));
+            }
+
+            for (start, end) in walls {
+                draw::thick_line(&mut image, start, end, thickness, BLACK);
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_hex() {
+        let mut grid = hex_grid::HexGrid::new(12, 10);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_hex.png");
+
+        assert!(std::path::Path::new("test_maze_hex.png").exists());
+    }
+}
@@ -0,0 +1,107 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_polygon_mut};
+use imageproc::point::Point;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use crate::maze::level_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the multi-level maze, with its floors side by side from the ground floor on the left,
+ * separated by the width of a cell. The stairs are marked by a triangle pointing upwards
+ * in the right half of the cell, or downwards in its left half.
+ */
+impl Drawable for level_grid::LevelGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as u32;
+        let size = (img.cell_size() + img.wall_thickness()) as u32;
+        let floor_width = self.width as u32 * size;
+
+        let width = self.levels as u32 * (floor_width + size) - size + thickness;
+        let height = self.height as u32 * size + thickness;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        for cell in self.cells.iter().flatten().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+
+            let x1 = c.level as u32 * (floor_width + size) + c.col as u32 * size;
+            let y1 = c.row as u32 * size;
+            let (x2, y2) = (x1 + size, y1 + size);
+
+            let is_wall = |side: &Option<Weak<_>>| match side {
+                Some(n) => !c.is_linked_to(Weak::clone(n)),
+                None => true,
+            };
+
+            // draws the top and left walls along the border, and all internal walls
+            if c.north.is_none() {
+                draw_filled_rect_mut(&mut image, Rect::at(x1 as i32, y1 as i32)
+                    .of_size(size + thickness, thickness), BLACK);
+            }
+            if c.west.is_none() {
+                draw_filled_rect_mut(&mut image, Rect::at(x1 as i32, y1 as i32)
+                    .of_size(thickness, size + thickness), BLACK);
+            }
+            if is_wall(&c.south) {
+                draw_filled_rect_mut(&mut image, Rect::at(x1 as i32, y2 as i32)
+                    .of_size(size + thickness, thickness), BLACK);
+            }
+            if is_wall(&c.east) {
+                draw_filled_rect_mut(&mut image, Rect::at(x2 as i32, y1 as i32)
+                    .of_size(thickness, size + thickness), BLACK);
+            }
+
+            // the markers fill the middle third of the cell's height
+            let (x1, y1) = (x1 as i32 + thickness as i32, y1 as i32 + thickness as i32);
+            let inner = (size - thickness) as i32;
+            let (top, bottom) = (y1 + inner / 3, y1 + inner * 2 / 3);
+            let half = inner / 2;
+
+            if c.up.is_some() && !is_wall(&c.up) {
+                draw_polygon_mut(&mut image, &[
+                    Point::new(x1 + half + half / 6, bottom),
+                    Point::new(x1 + inner - half / 6, bottom),
+                    Point::new(x1 + half + half / 2, top),
+                ], BLACK);
+            }
+            if c.down.is_some() && !is_wall(&c.down) {
+                draw_polygon_mut(&mut image, &[
+                    Point::new(x1 + half / 6, top),
+                    Point::new(x1 + half - half / 6, top),
+                    Point::new(x1 + half / 2, bottom),
+                ], BLACK);
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_levels() {
+        let mut grid = level_grid::LevelGrid::new(3, 8, 8);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_levels.png");
+
+        let saved = image::open("test_maze_levels.png").unwrap();
+        assert_eq!((3 * 8 * 22 + 2 * 22 + 2, 8 * 22 + 2), (saved.width(), saved.height()));
+    }
+}
@@ -1,2 +1,17 @@
+#[allow(clippy::module_inception)]
 pub mod presenter;
 pub mod to_img;
+pub mod draw;
+pub mod polar_img;
+pub mod hex_img;
+pub mod tri_img;
+pub mod upsilon_img;
+pub mod zeta_img;
+pub mod weave_img;
+pub mod level_img;
+pub mod cube_img;
+pub mod sphere_img;
+pub mod voronoi_img;
+pub mod penrose_img;
+pub mod graph_img;
+pub mod nested_img;
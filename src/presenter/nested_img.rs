@@ -0,0 +1,148 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use crate::maze::nested_grid::{NestedGrid, Side};
+use crate::maze::topology::MazeCell;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the nested maze with all of its levels, each sub-maze filling the inside of its cell.
+ * The cell size and the wall thickness of the image apply to the innermost mazes, while the walls
+ * grow thicker by the same thickness with every level up. The passages of the outer levels are
+ * drawn as walls too, with a gap at the door leading through the innermost mazes.
+ */
+impl Drawable for NestedGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = thickness(img, self.depth);
+        let width = self.width as u32 * pitch(img, self.width, self.depth) + thickness;
+        let height = self.height as u32 * pitch(img, self.height, self.depth) + thickness;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        draw_level(self, &mut image, img, (0, 0));
+
+        image
+    }
+}
+
+
+// the thickness of the walls of the maze of the specified depth
+fn thickness(img: &Image, depth: u16) -> u32 {
+    img.wall_thickness() as u32 * depth as u32
+}
+
+
+// the distance between the cells of the maze of the specified depth, along the side 'len' cells long
+fn pitch(img: &Image, len: u16, depth: u16) -> u32 {
+    if depth == 1 {
+        return (img.cell_size() + img.wall_thickness()) as u32;
+    }
+
+    // the sub-maze fills the inside of the cell, while its outer walls lie within the walls of the cell
+    let inside = len as u32 * pitch(img, len, depth - 1) - thickness(img, depth - 1);
+    inside + thickness(img, depth)
+}
+
+
+// draws the maze, whose top left wall starts at the origin, and all of its sub-mazes
+fn draw_level(grid: &NestedGrid, image: &mut RgbImage, img: &Image, origin: (i32, i32)) {
+    let t = thickness(img, grid.depth);
+    let (px, py) = (pitch(img, grid.width, grid.depth), pitch(img, grid.height, grid.depth));
+
+    for cell in grid.grid.cells.iter().flatten().flatten() {
+        let c = RefCell::borrow(cell);
+        let (row, col) = (c.row as usize, c.col as usize);
+        let x1 = origin.0 + (col as u32 * px) as i32;
+        let y1 = origin.1 + (row as u32 * py) as i32;
+        let (x2, y2) = (x1 + px as i32, y1 + py as i32);
+
+        // the openings of the maze are left out of its outer walls, as the door through
+        // them is cut by the outer level
+        let is_open = |side: Side, k: usize| grid.opening(side).is_some_and(|p| p[0] as usize == k);
+        if c.top.is_none() && !is_open(Side::North, col) {
+            fill(image, x1, y1, px + t, t, BLACK);
+        }
+        if c.left.is_none() && !is_open(Side::West, row) {
+            fill(image, x1, y1, t, py + t, BLACK);
+        }
+        if c.bottom.is_some() || !is_open(Side::South, col) {
+            fill(image, x1, y2, px + t, t, BLACK);
+        }
+        if c.right.is_some() || !is_open(Side::East, row) {
+            fill(image, x2, y1, t, py + t, BLACK);
+        }
+
+        if grid.depth == 1 {
+            // the innermost mazes show their passages as usual
+            for n in c.links() {
+                let n = n.upgrade().unwrap();
+                let n = RefCell::borrow(&n);
+                if n.col > c.col {
+                    fill(image, x2, y1 + t as i32, t, py - t, WHITE);
+                } else if n.row > c.row {
+                    fill(image, x1 + t as i32, y2, px - t, t, WHITE);
+                }
+            }
+            continue;
+        }
+
+        let child = &grid.children[row][col];
+
+        // cuts the doors of the passages to the right and the bottom cell through the wall
+        if let Some(door) = child.opening(Side::East).filter(|_| c.right.is_some()) {
+            let y = y1 + t as i32 + offset(img, door, grid.height, grid.depth) as i32;
+            fill(image, x2, y, t, img.cell_size() as u32, WHITE);
+        }
+        if let Some(door) = child.opening(Side::South).filter(|_| c.bottom.is_some()) {
+            let x = x1 + t as i32 + offset(img, door, grid.width, grid.depth) as i32;
+            fill(image, x, y2, img.cell_size() as u32, t, WHITE);
+        }
+
+        // the sub-maze fills the inside of the cell
+        let inset = t as i32 - thickness(img, grid.depth - 1) as i32;
+        draw_level(child, image, img, (x1 + inset, y1 + inset));
+    }
+}
+
+
+// fills the rectangle with the colour
+fn fill(image: &mut RgbImage, x: i32, y: i32, w: u32, h: u32, colour: image::Rgb<u8>) {
+    draw_filled_rect_mut(image, Rect::at(x, y).of_size(w, h), colour);
+}
+
+
+// the distance of the innermost cell of the door from the inside edge of the cell along the side
+fn offset(img: &Image, door: &[u16], len: u16, depth: u16) -> u32 {
+    door.iter().enumerate()
+        .map(|(i, &k)| k as u32 * pitch(img, len, depth - 1 - i as u16))
+        .sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Algo;
+    use crate::algos::nested::build_maze;
+
+
+    #[test]
+    fn draw_nested() {
+        let mut grid = NestedGrid::new(3, 3, 3);
+        grid.init_grid();
+        build_maze(&Algo::RecursiveBacktracking, &mut grid, &mut rand::thread_rng());
+
+        let img = Image::new(8, 1);
+        img.save(&grid, "test_maze_nested.png");
+
+        // the innermost cells are 9 px apart, the cells of the middle level 3 x 9 - 1 + 2 = 28 px,
+        // and the outer ones 3 x 28 - 2 + 3 = 85 px, within the outer walls 3 px thick
+        let saved = image::open("test_maze_nested.png").unwrap();
+        assert_eq!((3 * 85 + 3, 3 * 85 + 3), (saved.width(), saved.height()));
+    }
+}
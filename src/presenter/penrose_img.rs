@@ -0,0 +1,74 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use crate::maze::penrose_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the maze on the Penrose tiling, whose rhombs have sides as long as the cells
+ * of the rectangular maze are wide, so the image grows with the depth of the tiling.
+ */
+impl Drawable for penrose_grid::PenroseGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+
+        // the wheel of the tiling has the unit radius
+        let scale = size / self.side();
+        let width = (2.0 * scale + thickness).ceil() as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, width);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, width), WHITE);
+
+        let to_px = |(x, y): (f64, f64)| (scale * (1.0 + x) + thickness / 2.0,
+                                          scale * (1.0 - y) + thickness / 2.0);
+
+        for cell in &self.cells {
+            let c = RefCell::borrow(cell);
+
+            for (k, side) in c.sides.iter().enumerate() {
+                // draws every wall once, from the cell which comes first
+                let is_wall = match side {
+                    Some(n) => RefCell::borrow(&n.upgrade().unwrap()).index > c.index
+                        && !c.is_linked_to(Weak::clone(n)),
+                    None => true,
+                };
+
+                if is_wall {
+                    draw::thick_line(&mut image, to_px(c.corners[k]), to_px(c.corners[(k + 1) % 4]),
+                                     thickness, BLACK);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_penrose() {
+        let mut grid = penrose_grid::PenroseGrid::new(5);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_penrose.png");
+
+        assert!(std::path::Path::new("test_maze_penrose.png").exists());
+    }
+}
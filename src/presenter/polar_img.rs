@@ -0,0 +1,88 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::f64::consts::PI;
+use std::rc::Rc;
+use crate::maze::circ_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the circular maze, where each ring is as high as a cell in the rectangular maze.
+ * The walls between the rings are drawn as arcs, and the walls within a ring as radial lines.
+ */
+impl Drawable for circ_grid::CircGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let ring_height = (img.cell_size() + img.wall_thickness()) as f64;
+        let thickness = img.wall_thickness() as f64;
+
+        let radius = self.rings as f64 * ring_height;
+        let size = (2.0 * (radius + thickness)).ceil() as u32;
+        let centre = (size as f64 / 2.0, size as f64 / 2.0);
+
+        let mut image: RgbImage = ImageBuffer::new(size, size);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(size, size), WHITE);
+
+        for ring in self.cells.iter().skip(1) {
+            let theta = 2.0 * PI / ring.len() as f64;
+
+            for cell in ring.iter() {
+                let cell = Rc::clone(cell.as_ref().unwrap());
+                let c = RefCell::borrow(&cell);
+
+                let inner_radius = c.row as f64 * ring_height;
+                let outer_radius = (c.row + 1) as f64 * ring_height;
+                let theta_ccw = c.col as f64 * theta;
+                let theta_cw = (c.col + 1) as f64 * theta;
+
+                // draws the wall towards the inner ring
+                if !c.is_linked_to(Rc::downgrade(&c.inward.as_ref().unwrap().upgrade().unwrap())) {
+                    draw::arc(&mut image, centre, inner_radius, theta_ccw, theta_cw, thickness, BLACK);
+                }
+
+                // draws the wall towards the clockwise neighbour
+                if let Some(cw) = &c.cw {
+                    if !c.is_linked_to(Rc::downgrade(&cw.upgrade().unwrap())) {
+                        let (cos, sin) = (theta_cw.cos(), theta_cw.sin());
+                        draw::thick_line(&mut image,
+                                         (centre.0 + inner_radius * cos, centre.1 + inner_radius * sin),
+                                         (centre.0 + outer_radius * cos, centre.1 + outer_radius * sin),
+                                         thickness, BLACK);
+                    }
+                }
+            }
+        }
+
+        // draws the outer border
+        draw::arc(&mut image, centre, radius, 0.0, 2.0 * PI, thickness, BLACK);
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_polar() {
+        let mut grid = circ_grid::CircGrid::new(10);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_polar.png");
+
+        let saved = image::open("test_maze_polar.png").unwrap();
+        assert_eq!((444, 444), (saved.width(), saved.height()));
+    }
+}
@@ -1,45 +1,68 @@
-use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::rc::Rc;
 use crate::maze::{cell, grid};
+use crate::maze::topology::MazeCell;
 
 
+// only used when debugging, e.g. by the test below
+#[allow(dead_code)]
 pub fn print_to_console(grid: &grid::Grid) {
+    print!("{}", to_string(grid));
+}
+
+
+/*
+ * Draws the maze as text, the same way as it is printed to the console.
+ */
+pub fn to_string(grid: &grid::Grid) -> String {
     let corner: &'static str = "+";
     let body: &'static str = "    ";
     let rbndr_pass: &'static str = " "; // passage to right adjacent cell
     let rbndr_wall: &'static str = "|"; // wall to right adjacent cell
     let bbndr_pass: &'static str = "    "; // passage to bottom adjacent cell
     let bbndr_wall: &'static str = "----"; // wall to bottom adjacent cell
+    let missing: &'static str = "XXXX"; // cell left out by the mask
+    let (rbndr_east, rbndr_west) = (">", "<"); // one-way passage to or from right adjacent cell
+    let (bbndr_south, bbndr_north) = (" vv ", " ^^ "); // one-way passage to or from bottom adjacent cell
 
-    let output = "+----".repeat(grid.width as usize) + corner;
-    println!("{}", output);
+    let mut output = "+----".repeat(grid.width as usize) + corner + "\n";
 
     for row in 0..grid.height as usize {
         let mut line_one = String::from("|");
         let mut line_two = String::from("+");
 
         for col in 0..grid.width as usize {
-            let cell = Rc::clone(&grid.cells[row][col].as_ref().unwrap());
-            let c = (&*cell).borrow();
+            // the cells left out by the mask are walled off from all sides
+            let Some(cell) = grid.cells[row][col].as_ref().map(Rc::clone) else {
+                line_one += missing;
+                line_one += rbndr_wall;
+                line_two += bbndr_wall;
+                line_two += corner;
+                continue;
+            };
+            let c = (*cell).borrow();
 
             // let right = &*c.right.as_ref().unwrap();
             line_one += body;
             if let Some(right) = &c.right {
-                if (&*cell).borrow().is_linked_to(Rc::downgrade(&right.upgrade().unwrap())) {
-                    line_one += rbndr_pass;
-                } else {
-                    line_one += rbndr_wall;
-                }
+                line_one += match passage(&cell, &right.upgrade().unwrap()) {
+                    (true, true) => rbndr_pass,
+                    (true, false) => rbndr_east,
+                    (false, true) => rbndr_west,
+                    (false, false) => rbndr_wall,
+                };
             } else {
                 line_one += rbndr_wall;
             }
 
             // let bottom = &*c.bottom.as_ref().unwrap();
             if let Some(bottom) = &c.bottom {
-                if (&*cell).borrow().is_linked_to(Rc::downgrade(&bottom.upgrade().unwrap())) {
-                    line_two += bbndr_pass;
-                } else {
-                    line_two += bbndr_wall;
-                }
+                line_two += match passage(&cell, &bottom.upgrade().unwrap()) {
+                    (true, true) => bbndr_pass,
+                    (true, false) => bbndr_south,
+                    (false, true) => bbndr_north,
+                    (false, false) => bbndr_wall,
+                };
             } else {
                 line_two += bbndr_wall;
             }
@@ -47,14 +70,22 @@ pub fn print_to_console(grid: &grid::Grid) {
 
         }
 
-        println!("{}", line_one);
-        println!("{}", line_two);
+        output += &format!("{}\n{}\n", line_one, line_two);
     }
+
+    output
+}
+
+
+// checks if the passage between both cells leads from the first one, and to the first one
+fn passage(a: &cell::CellStrongLink, b: &cell::CellStrongLink) -> (bool, bool) {
+    (RefCell::borrow(a).is_linked_to(Rc::downgrade(b)), RefCell::borrow(b).is_linked_to(Rc::downgrade(a)))
 }
 
 
 // needs to run with 'cargo test -- --nocapture'
 #[cfg(test)]
+#[allow(dead_code, clippy::assertions_on_constants)]
 mod tests {
     use super::*;
 
@@ -68,4 +99,27 @@ mod tests {
 
         assert!(true);
     }
+
+
+    #[test]
+    fn to_string_one_way() {
+        let mut grid = grid::Grid::new(2, 2);
+        grid.init_grid();
+        let cell = |row: usize, col: usize| grid.cells[row][col].as_ref().unwrap();
+        RefCell::borrow_mut(cell(0, 0)).link_to(Rc::downgrade(cell(0, 1)));
+        RefCell::borrow_mut(cell(1, 1)).link_to(Rc::downgrade(cell(0, 1)));
+
+        assert_eq!("+----+----+\n|    >    |\n+----+ ^^ +\n|    |    |\n+----+----+\n",
+                   to_string(&grid));
+    }
+
+
+    #[test]
+    fn to_string_masked() {
+        let mut grid = grid::Grid::with_mask(crate::maze::mask::Mask::parse("..\nX.\n"));
+        grid.init_grid();
+
+        assert_eq!("+----+----+\n|    |    |\n+----+----+\n|XXXX|    |\n+----+----+\n",
+                   to_string(&grid));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use crate::maze::sphere_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the spherical maze as an equirectangular texture, twice as wide as it is high, which
+ * maps the longitudes onto the columns and the latitudes onto the rows of the image. The walls
+ * crossing the longitude seam are split between both sides of the image, so the texture wraps
+ * seamlessly around a globe. The poles are single points, hence they have no walls.
+ */
+impl Drawable for sphere_grid::SphereGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+
+        let height = (self.rows as f64 * size) as u32;
+        let width = 2 * height;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        // draws a wall centred on the line between both points, along the rows or the columns
+        let mut wall = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| {
+            let left = (x1.min(x2) - thickness / 2.0).round() as i32;
+            let top = (y1.min(y2) - thickness / 2.0).round() as i32;
+            let w = ((x2 - x1).abs() + thickness).round().max(1.0) as u32;
+            let h = ((y2 - y1).abs() + thickness).round().max(1.0) as u32;
+
+            // the parts of the wall overflowing the image are drawn on the opposite side
+            for dx in [-(width as i32), 0, width as i32] {
+                draw_filled_rect_mut(&mut image, Rect::at(left + dx, top).of_size(w, h), BLACK);
+            }
+        };
+
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                let c = RefCell::borrow(cell.as_ref().unwrap());
+
+                let (start, end) = self.longitudes(row, col);
+                let x2 = end * width as f64;
+                let (y1, y2) = (row as f64 * size, (row + 1) as f64 * size);
+
+                if !c.is_linked_to(Weak::clone(c.east.as_ref().unwrap())) {
+                    wall((x2, y1), (x2, y2));
+                }
+
+                for south in &c.south {
+                    if c.is_linked_to(Weak::clone(south)) {
+                        continue;
+                    }
+
+                    // the wall runs along the longitudes shared by both cells
+                    let (s_row, s_col) = {
+                        let s = south.upgrade().unwrap();
+                        let s = RefCell::borrow(&s);
+                        (s.row as usize, s.col as usize)
+                    };
+                    let (s_start, s_end) = self.longitudes(s_row, s_col);
+                    wall((start.max(s_start) * width as f64, y2), (end.min(s_end) * width as f64, y2));
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_sphere() {
+        let mut grid = sphere_grid::SphereGrid::new(12);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_sphere.png");
+
+        let saved = image::open("test_maze_sphere.png").unwrap();
+        assert_eq!((2 * 12 * 22, 12 * 22), (saved.width(), saved.height()));
+    }
+}
@@ -1,9 +1,28 @@
 use image::{ImageBuffer, Rgb, RgbImage};
-use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::drawing::{draw_filled_circle_mut, draw_filled_rect_mut, draw_polygon_mut};
+use imageproc::point::Point;
 use imageproc::rect::Rect;
 
-use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use std::rc::Rc;
 use crate::maze::{cell, grid};
+use crate::maze::topology::MazeCell;
+use crate::presenter::{draw, zeta_img};
+
+
+// colours specified
+pub const WHITE: Rgb<u8> = Rgb([240, 240, 240]);
+pub const BLACK: Rgb<u8> = Rgb([0, 0, 0]);
+
+// the colour of the arrows in the one-way openings
+const ONE_WAY_COLOUR: Rgb<u8> = Rgb([220, 50, 47]);
+
+// the colours of the markers pairing up the passages across the seams of a wrapped maze,
+// or the ends of the portals
+const MARKER_COLOURS: [Rgb<u8>; 6] = [
+    Rgb([220, 50, 47]), Rgb([38, 139, 210]), Rgb([133, 153, 0]),
+    Rgb([211, 54, 130]), Rgb([181, 137, 0]), Rgb([42, 161, 152]),
+];
 
 
 pub struct Image {
@@ -21,7 +40,7 @@ impl Image {
             cell_size,
             wall_thickness,
 
-            size: (cell_size + wall_thickness) as u16,
+            size: cell_size + wall_thickness,
 
         }
     }
@@ -32,69 +51,254 @@ impl Image {
     }
 
 
-    pub fn save(&self, grid: &grid::Grid, filename: &str) {
-        let width = self.set_size(grid.width);
-        let height= self.set_size(grid.height);
+    // the walls along the seam of a toroidal maze are shared by both of its edges,
+    // hence the closing wall is left out so the image can be tiled seamlessly
+    fn set_wrapped_size(&self, size: u16) -> u32 {
+        (size * self.size) as u32
+    }
+
+
+    /*
+     * Returns the size of the cells in pixels.
+     */
+    pub fn cell_size(&self) -> u16 {
+        self.cell_size
+    }
+
 
-        // colours specified
-        let white = Rgb::from([240, 240, 240]);
-        let black = Rgb::from([0, 0, 0]);
+    /*
+     * Returns the thickness of the walls in pixels.
+     */
+    pub fn wall_thickness(&self) -> u16 {
+        self.wall_thickness
+    }
+
+
+    pub fn save<G: Drawable + ?Sized>(&self, grid: &G, filename: &str) {
+        grid.draw(self).save(filename).unwrap();
+    }
+}
+
+
+/*
+ * The interface of all mazes, which can be drawn into an image.
+ */
+pub trait Drawable {
+    fn draw(&self, img: &Image) -> RgbImage;
+}
 
-        let mut img: RgbImage = ImageBuffer::new(width, height);
+
+impl Drawable for grid::Grid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        if self.diagonal {
+            return zeta_img::draw(self, img);
+        }
+
+        // only the toroidal maze is drawn as a seamless tile, the others show their seams
+        let tileable = self.boundary == grid::Boundary::Torus;
+
+        let (width, height) = if tileable {
+            (img.set_wrapped_size(self.width), img.set_wrapped_size(self.height))
+        } else {
+            (img.set_size(self.width), img.set_size(self.height))
+        };
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
         // clears the background and sets it to a WHITE
-        draw_filled_rect_mut(&mut img,
+        draw_filled_rect_mut(&mut image,
                              Rect::at(0, 0).of_size(width, height),
-                             white);
+                             WHITE);
 
-        for row in 0..(grid.height) as usize {
-            for col in 0..(grid.width ) as usize {
-                let cell = Rc::clone(&grid.cells[row][col].as_ref().unwrap());
-                let c = (&*cell).borrow();
+        for row in 0..(self.height) as usize {
+            for col in 0..(self.width ) as usize {
+                // the cells left out by the mask are not drawn, and the walls of
+                // the adjacent cells enclose them
+                let Some(cell) = self.cells[row][col].as_ref().map(Rc::clone) else {
+                    continue;
+                };
+                let c = (*cell).borrow();
 
-                let x1 = (c.col * self.size) as u32;
-                let y1 = (c.row * self.size) as u32;
-                let x2 = ((c.col + 1) * self.size) as u32;
-                let y2 = ((c.row + 1) * self.size) as u32;
+                let x1 = (c.col * img.size) as u32;
+                let y1 = (c.row * img.size) as u32;
+                let x2 = ((c.col + 1) * img.size) as u32;
+                let y2 = ((c.row + 1) * img.size) as u32;
 
-                // draws a line with the specific thickness stored in 'self.wall_thickness'
+                // draws a line with the specific thickness stored in 'img.wall_thickness'
                 let mut draw_line = |sx: u32, sy: u32, ex: u32, ey: u32| {
-                    if sx == ex {
-                        draw_filled_rect_mut(&mut img,
-                                             Rect::at(sx as i32, sy as i32).of_size(
-                                                 self.wall_thickness as u32,
-                                                 (ey - sy) + self.wall_thickness as u32),
-                                             black);
+                    let rect = if sx == ex {
+                        Rect::at(sx as i32, sy as i32).of_size(
+                            img.wall_thickness as u32,
+                            (ey - sy) + img.wall_thickness as u32)
                     } else {
-                        draw_filled_rect_mut(&mut img,
-                                             Rect::at(sx as i32, sy as i32).of_size(
-                                                 (ex - sx) + self.wall_thickness as u32,
-                                                 self.wall_thickness as u32),
-                                             black);
+                        Rect::at(sx as i32, sy as i32).of_size(
+                            (ex - sx) + img.wall_thickness as u32,
+                            img.wall_thickness as u32)
+                    };
+                    draw_filled_rect_mut(&mut image, rect, BLACK);
+
+                    // on a toroidal maze, the parts of a wall overflowing the image
+                    // are drawn on the opposite side
+                    if tileable {
+                        for (dx, dy) in [(width as i32, 0), (0, height as i32),
+                                         (width as i32, height as i32)] {
+                            draw_filled_rect_mut(&mut image,
+                                                 Rect::at(rect.left() - dx, rect.top() - dy)
+                                                     .of_size(rect.width(), rect.height()),
+                                                 BLACK);
+                        }
                     }
                 };
 
-                // draws the top and left walls
-                if c.top.is_none() {
+                // draws the top and left walls, which also lie along the seams of a wrapped maze
+                let is_seam_wall = |side: &Option<cell::CellWeakLink>| !tileable && side.as_ref()
+                    .is_some_and(|n| !cell::is_open(&cell, &n.upgrade().unwrap()));
+                if c.top.is_none() || c.row == 0 && is_seam_wall(&c.top) {
                     draw_line(x1, y1, x2, y1);
                 }
-                if c.left.is_none() {
+                if c.left.is_none() || c.col == 0 && is_seam_wall(&c.left) {
                     draw_line(x1, y1, x1, y2);
                 }
 
-                // draws all internal walls
-                if c.bottom.as_ref().is_none() || !c.is_linked_to(Rc::downgrade(
-                        &c.bottom.as_ref().unwrap().upgrade().unwrap())) {
+                // draws all internal walls, where there is no passage leading either way
+                if c.bottom.as_ref().is_none() || !cell::is_open(&cell,
+                        &c.bottom.as_ref().unwrap().upgrade().unwrap()) {
                     draw_line(x1, y2, x2, y2);
                 }
-                if c.right.as_ref().is_none() || !c.is_linked_to(Rc::downgrade(
-                        &c.right.as_ref().unwrap().upgrade().unwrap())) {
+                if c.right.as_ref().is_none() || !cell::is_open(&cell,
+                        &c.right.as_ref().unwrap().upgrade().unwrap()) {
                     draw_line(x2, y1, x2, y2);
                 }
 
             }
         }
 
-        img.save(filename).unwrap();
+        if !tileable {
+            self.draw_seam_markers(&mut image, img);
+        }
+        self.draw_one_way_arrows(&mut image, img);
+        self.draw_portals(&mut image, img);
+
+        image
+    }
+}
+
+
+impl grid::Grid {
+    // draws an arrow into each one-way opening, pointing the way the passage leads
+    pub(crate) fn draw_one_way_arrows(&self, image: &mut RgbImage, img: &Image) {
+        let length = (img.cell_size as f64 / 3.0).max(3.0);
+        let centre = |index: u16| index as f64 * img.size as f64 + (img.size + img.wall_thickness) as f64 / 2.0;
+
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            // each opening is visited once, from the cell above or on the left of it
+            for (side, (dx, dy)) in [(&c.right, (1.0, 0.0)), (&c.bottom, (0.0, 1.0)),
+                                     (&c.bottom_right, (1.0, 1.0)), (&c.bottom_left, (-1.0, 1.0))] {
+                let Some(next) = side.as_ref().map(|n| n.upgrade().unwrap()) else {
+                    continue;
+                };
+                let (forward, backward) = (c.is_linked_to(Rc::downgrade(&next)),
+                                           RefCell::borrow(&next).is_linked_to(Rc::downgrade(cell)));
+                if forward == backward {
+                    continue;
+                }
+
+                // the arrow lies in the opening, halfway between the centres of both cells
+                let sign = if forward { 1.0 } else { -1.0 };
+                let norm = f64::hypot(dx, dy);
+                let (ux, uy) = (sign * dx / norm, sign * dy / norm);
+                let (mx, my) = (centre(c.col) + dx * img.size as f64 / 2.0,
+                                centre(c.row) + dy * img.size as f64 / 2.0);
+                let half = length / 2.0;
+
+                let points = [(mx + ux * half, my + uy * half),
+                              (mx - ux * half - uy * half, my - uy * half + ux * half),
+                              (mx - ux * half + uy * half, my - uy * half - ux * half)]
+                    .map(|(x, y)| Point::new(x.round() as i32, y.round() as i32));
+                draw_polygon_mut(image, &points, ONE_WAY_COLOUR);
+            }
+        }
+    }
+
+
+    // marks both ends of each portal with a disc of the same colour, labelled with the number
+    // of the pair, as the colours repeat when there are many portals
+    pub(crate) fn draw_portals(&self, image: &mut RgbImage, img: &Image) {
+        let radius = img.cell_size as i32 * 2 / 5;
+        let centre = |index: u16| index as f64 * img.size as f64 + (img.size + img.wall_thickness) as f64 / 2.0;
+
+        let mut pairs = 0;
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            let Some(other) = c.portal.as_ref().map(|p| p.upgrade().unwrap()) else {
+                continue;
+            };
+            let o = RefCell::borrow(&other);
+            // draws each pair once, from the end coming first
+            if (o.row, o.col) < (c.row, c.col) {
+                continue;
+            }
+
+            pairs += 1;
+            let colour = MARKER_COLOURS[(pairs - 1) % MARKER_COLOURS.len()];
+            let label = pairs.to_string();
+            // the label is only written, when it fits into the disc
+            let fits = (label.len() * 6) as i32 <= 2 * radius - 2 && 7 <= 2 * radius - 2;
+
+            for (row, col) in [(c.row, c.col), (o.row, o.col)] {
+                let (x, y) = (centre(col), centre(row));
+                draw_filled_circle_mut(image, (x as i32, y as i32), radius, colour);
+                if fits {
+                    draw::text(image, (x, y), &label, 1, WHITE);
+                }
+            }
+        }
+    }
+
+
+    // marks both ends of each passage across the seams with the same colour, so it is clear
+    // where the passage leads, e.g. upside down on a Mobius strip
+    fn draw_seam_markers(&self, image: &mut RgbImage, img: &Image) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let marker = (img.cell_size / 4).max(img.wall_thickness) as u32;
+        let edge = |index: usize| (index as u16 * img.size + img.wall_thickness) as i32;
+        let far = |len: usize| (len as u16 * img.size + img.wall_thickness) as i32 - marker as i32;
+
+        let mut colours = MARKER_COLOURS.iter().cycle();
+
+        // the passages leading from the right edge to the left, and from the bottom edge to the top
+        for (cells, horizontal) in [((0..height).map(|r| (r, width - 1)).collect::<Vec<_>>(), true),
+                                    ((0..width).map(|c| (height - 1, c)).collect(), false)] {
+            for (row, col) in cells {
+                let Some(c) = self.cells[row][col].as_ref().map(|c| RefCell::borrow(c)) else {
+                    continue;
+                };
+                let side = if horizontal { &c.right } else { &c.bottom };
+                let Some(next) = side.as_ref().map(|n| n.upgrade().unwrap()) else {
+                    continue;
+                };
+                let n = RefCell::borrow(&next);
+                if !(horizontal && n.col == 0 || !horizontal && n.row == 0)
+                    || !cell::is_open(self.cells[row][col].as_ref().unwrap(), &next) {
+                    continue;
+                }
+
+                let colour = *colours.next().unwrap();
+                let (from, to) = if horizontal {
+                    (Rect::at(far(width), edge(row)), Rect::at(0, edge(n.row as usize)))
+                } else {
+                    (Rect::at(edge(col), far(height)), Rect::at(edge(n.col as usize), 0))
+                };
+                let (w, h) = if horizontal {
+                    (marker, img.cell_size as u32)
+                } else {
+                    (img.cell_size as u32, marker)
+                };
+
+                draw_filled_rect_mut(image, from.of_size(w, h), colour);
+                draw_filled_rect_mut(image, to.of_size(w, h), colour);
+            }
+        }
     }
 }
 
@@ -102,6 +306,7 @@ impl Image {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::rc::Weak;
     use crate::algos::sidewinder::*;
 
 
@@ -110,11 +315,165 @@ mod tests {
         let mut grid = grid::Grid::new(10, 10);
         grid.init_grid();
 
-        build_maze(&grid);
+        build_maze(&grid, &mut rand::thread_rng());
 
         let img = Image::new(20, 2);
         img.save(&grid, "test_maze.png");
 
-        assert!(true);
+        assert!(std::path::Path::new("test_maze.png").exists());
+    }
+
+
+    #[test]
+    fn draw_mobius_seam_markers() {
+        let mut grid = grid::Grid::with_boundary(10, 8, grid::Boundary::Mobius);
+        grid.init_grid();
+
+        crate::algos::recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_mobius.png");
+
+        // the seam passages are not drawn as walls, so the image keeps its borders
+        let saved = image::open("test_maze_mobius.png").unwrap().to_rgb8();
+        assert_eq!((222, 178), (saved.width(), saved.height()));
+
+        // every passage across the seam is marked on both edges
+        let crossings = (0..8).filter(|&row| {
+            let c = RefCell::borrow(grid.cells[row][9].as_ref().unwrap());
+            c.is_linked_to(Weak::clone(c.right.as_ref().unwrap()))
+        }).count();
+        let marked = (0..8).filter(|&row| {
+            let pixel = *saved.get_pixel(1, (row * 22 + 12) as u32);
+            pixel != WHITE && pixel != BLACK
+        }).count();
+        assert_eq!(crossings, marked);
+    }
+
+
+    #[test]
+    fn draw_masked() {
+        let mask = crate::maze::mask::Mask::from_txt("tests/fixtures/masks/ring.txt").unwrap();
+        let mut grid = grid::Grid::with_mask(mask);
+        grid.init_grid();
+
+        crate::algos::recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_masked.png");
+
+        // the missing cells are left blank, and walled off from their neighbours
+        let saved = image::open("test_maze_masked.png").unwrap().to_rgb8();
+        assert_eq!(WHITE, *saved.get_pixel(1, 1));
+        assert_eq!(WHITE, *saved.get_pixel(3 * 22 + 12, 2 * 22 + 12));
+        assert_eq!(BLACK, *saved.get_pixel(3 * 22 + 12, 2 * 22));
+    }
+
+
+    #[test]
+    fn draw_image_masked() {
+        let mask = crate::maze::mask::Mask::from_img("tests/fixtures/masks/heart.png", 24, 20).unwrap();
+        let mut grid = grid::Grid::with_mask(mask);
+        grid.init_grid();
+
+        crate::algos::recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+
+        // the maze fills the whole silhouette
+        assert!(grid.cells.iter().flatten().flatten()
+            .all(|cell| RefCell::borrow(cell).has_linked_cells()));
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_heart.png");
+
+        let saved = image::open("test_maze_heart.png").unwrap().to_rgb8();
+        assert_eq!((24 * 22 + 2, 20 * 22 + 2), (saved.width(), saved.height()));
+        // nothing is drawn around the silhouette, e.g. in its top left corner
+        assert!((0..22).all(|x| (0..22).all(|y| *saved.get_pixel(x, y) == WHITE)));
+    }
+
+
+    #[test]
+    fn draw_shape_masked() {
+        use crate::maze::{mask::Mask, shape::Shape};
+
+        let star = Shape::star((15.0, 15.5), 15.0, 7.0, 5)
+            .with_hole(Shape::Circle { centre: (15.0, 15.5), radius: 3.0 });
+        let mut grid = grid::Grid::with_mask(Mask::from_shape(&star, 30, 30));
+        grid.init_grid();
+
+        crate::algos::recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_star.png");
+
+        // the hole in the middle of the star is left blank
+        let saved = image::open("test_maze_star.png").unwrap().to_rgb8();
+        assert_eq!(WHITE, *saved.get_pixel(15 * 22, 15 * 22 + 11));
+        assert!(grid.cells.iter().flatten().flatten()
+            .all(|cell| RefCell::borrow(cell).has_linked_cells()));
+    }
+
+
+    #[test]
+    fn draw_portals() {
+        let mut grid = grid::Grid::new(10, 10);
+        grid.init_grid();
+
+        crate::algos::recursive_backtracking::build_maze(&grid, &mut rand::thread_rng());
+        crate::algos::portals::place_portals(&grid, 8, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_portals.png");
+
+        // both ends of each pair are marked with the same colour
+        let saved = image::open("test_maze_portals.png").unwrap().to_rgb8();
+        for cell in grid.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+            let colour_at = |row: u16, col: u16| *saved.get_pixel(col as u32 * 22 + 5, row as u32 * 22 + 12);
+            match c.portal.as_ref().map(|p| p.upgrade().unwrap()) {
+                Some(other) => {
+                    let o = RefCell::borrow(&other);
+                    assert!(MARKER_COLOURS.contains(&colour_at(c.row, c.col)));
+                    assert_eq!(colour_at(c.row, c.col), colour_at(o.row, o.col));
+                }
+                None => assert!(!MARKER_COLOURS.contains(&colour_at(c.row, c.col))),
+            }
+        }
+    }
+
+
+    #[test]
+    fn draw_one_way() {
+        let mut grid = grid::Grid::new(3, 1);
+        grid.init_grid();
+        let (a, b, c) = (grid.cells[0][0].as_ref().unwrap(), grid.cells[0][1].as_ref().unwrap(),
+                         grid.cells[0][2].as_ref().unwrap());
+        RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+        RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+        RefCell::borrow_mut(c).link_to(Rc::downgrade(b));
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_one_way.png");
+
+        // the one-way opening is left open, with an arrow pointing to the left
+        let saved = image::open("test_maze_one_way.png").unwrap().to_rgb8();
+        assert_eq!(ONE_WAY_COLOUR, *saved.get_pixel(2 * 22 + 1, 12));
+        assert_eq!(WHITE, *saved.get_pixel(2 * 22 + 1, 4));
+        assert_eq!(WHITE, *saved.get_pixel(22 + 1, 12));
+    }
+
+
+    #[test]
+    fn draw_toroidal() {
+        let mut grid = grid::Grid::new_toroidal(10, 8);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_toroidal.png");
+
+        let saved = image::open("test_maze_toroidal.png").unwrap();
+        assert_eq!((220, 176), (saved.width(), saved.height()));
     }
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use crate::maze::tri_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the delta maze, where each side of a triangle is as long, as a cell in the rectangular
+ * maze is wide. The walls on the west and the base walls of the triangles pointing up are only
+ * drawn on the border, as they are shared with the cells drawing them otherwise.
+ */
+impl Drawable for tri_grid::TriGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+        let half_width = size / 2.0;
+        let height = size * 3f64.sqrt() / 2.0;
+        let half_height = height / 2.0;
+
+        let width = (half_width * (self.width + 1) as f64 + 2.0 * thickness).ceil() as u32;
+        let img_height = (height * self.height as f64 + 2.0 * thickness).ceil() as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, img_height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, img_height), WHITE);
+
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+
+            let cx = thickness + half_width + c.col as f64 * half_width;
+            let cy = thickness + half_height + c.row as f64 * height;
+
+            let (west_x, mid_x, east_x) = (cx - half_width, cx, cx + half_width);
+            let (apex_y, base_y) = if c.is_upright() {
+                (cy - half_height, cy + half_height)
+            } else {
+                (cy + half_height, cy - half_height)
+            };
+
+            let mut walls = vec![];
+            if c.west.is_none() {
+                walls.push(((west_x, base_y), (mid_x, apex_y)));
+            }
+            if c.east.as_ref().is_none() || !c.is_linked_to(Rc::downgrade(
+                    &c.east.as_ref().unwrap().upgrade().unwrap())) {
+                walls.push(((east_x, base_y), (mid_x, apex_y)));
+            }
+
+            let is_south_border = c.is_upright() && c.south.is_none();
+            let is_north_closed = !c.is_upright() && (c.north.as_ref().is_none() ||
+                !c.is_linked_to(Rc::downgrade(&c.north.as_ref().unwrap().upgrade().unwrap())));
+            if is_south_border || is_north_closed {
+                walls.push(((east_x, base_y), (west_x, base_y)));
+            }
+
+            for (start, end) in walls {
+                draw::thick_line(&mut image, start, end, thickness, BLACK);
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_delta() {
+        let mut grid = tri_grid::TriGrid::hexagon(5);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_delta.png");
+
+        assert!(std::path::Path::new("test_maze_delta.png").exists());
+    }
+}
@@ -0,0 +1,102 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::f64::consts::SQRT_2;
+use std::rc::Rc;
+use crate::maze::upsilon_cell::UpsilonCellWeakLink;
+use crate::maze::upsilon_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+// a side of a cell, given by the neighbour behind it and its end points relative to the centre
+type Side<'a> = (&'a Option<UpsilonCellWeakLink>, (f64, f64), (f64, f64));
+
+
+/*
+ * Draws the upsilon maze. The cells are centred on a square lattice, as wide as a cell in
+ * the rectangular maze, so the octagons touch each other diagonally, and the squares fill
+ * the gaps between them. A square is as wide as a side of an octagon.
+ */
+impl Drawable for upsilon_grid::UpsilonGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let pitch = (img.cell_size() + img.wall_thickness()) as f64;
+
+        // the distance of the octagon sides from its centre, and the half of its side
+        let half = pitch / SQRT_2;
+        let half_side = half / (1.0 + SQRT_2);
+
+        // the octagons on the border overflow the lattice
+        let margin = thickness + half - pitch / 2.0;
+        let width = (pitch * self.width as f64 + 2.0 * margin).ceil() as u32;
+        let height = (pitch * self.height as f64 + 2.0 * margin).ceil() as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+
+            let cx = margin + (c.col as f64 + 0.5) * pitch;
+            let cy = margin + (c.row as f64 + 0.5) * pitch;
+
+            // the distance of the orthogonal sides from the centre
+            let (h, s) = if c.is_octagon() { (half, half_side) } else { (half_side, half_side) };
+
+            let mut sides: Vec<Side> = vec![
+                (&c.north, (-s, -h), (s, -h)),
+                (&c.east, (h, -s), (h, s)),
+                (&c.south, (s, h), (-s, h)),
+                (&c.west, (-h, s), (-h, -s)),
+            ];
+            if c.is_octagon() {
+                sides.extend([
+                    (&c.northeast, (s, -h), (h, -s)),
+                    (&c.southeast, (h, s), (s, h)),
+                    (&c.southwest, (-s, h), (-h, s)),
+                    (&c.northwest, (-h, -s), (-s, -h)),
+                ]);
+            }
+
+            for (neighbour, start, end) in sides {
+                let is_open = match neighbour {
+                    Some(n) => c.is_linked_to(Rc::downgrade(&n.upgrade().unwrap())),
+                    None => false,
+                };
+
+                if !is_open {
+                    draw::thick_line(&mut image, (cx + start.0, cy + start.1),
+                                     (cx + end.0, cy + end.1), thickness, BLACK);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_upsilon() {
+        let mut grid = upsilon_grid::UpsilonGrid::new(12, 12);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_upsilon.png");
+
+        assert!(std::path::Path::new("test_maze_upsilon.png").exists());
+    }
+}
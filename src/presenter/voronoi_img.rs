@@ -0,0 +1,76 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use std::rc::Weak;
+use crate::maze::voronoi_grid;
+use crate::maze::topology::MazeCell;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the irregular maze, whose walls are the edges shared by the regions of adjacent cells,
+ * unless there is a passage between them, enclosed by the border of the rectangle.
+ */
+impl Drawable for voronoi_grid::VoronoiGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+
+        let width = (self.width as f64 * size + thickness) as u32;
+        let height = (self.height as f64 * size + thickness) as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        let to_px = |(x, y): (f64, f64)| (x * size + thickness / 2.0, y * size + thickness / 2.0);
+
+        let (w, h) = (self.width as f64, self.height as f64);
+        draw::thick_polyline(&mut image, &[(0.0, 0.0), (w, 0.0), (w, h), (0.0, h), (0.0, 0.0)]
+                                 .map(to_px), thickness, BLACK);
+
+        for cell in &self.cells {
+            let c = RefCell::borrow(cell);
+
+            for neighbour in &c.adjacent {
+                let n = neighbour.upgrade().unwrap();
+                let n = RefCell::borrow(&n);
+
+                // draws every wall once, from the cell which comes first
+                if n.index < c.index || c.is_linked_to(Weak::clone(neighbour)) {
+                    continue;
+                }
+                if let Some((a, b)) = voronoi_grid::shared_edge(&c.polygon, c.site, n.site) {
+                    draw::thick_line(&mut image, to_px(a), to_px(b), thickness, BLACK);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_voronoi() {
+        let mut grid = voronoi_grid::VoronoiGrid::poisson(14, 10, &mut rand::thread_rng());
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_voronoi.png");
+
+        let saved = image::open("test_maze_voronoi.png").unwrap();
+        assert_eq!((14 * 22 + 2, 10 * 22 + 2), (saved.width(), saved.height()));
+    }
+}
@@ -0,0 +1,109 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use std::cell::RefCell;
+use crate::maze::weave_grid;
+use crate::presenter::draw;
+use crate::presenter::to_img::{Drawable, Image, BLACK, WHITE};
+
+
+/*
+ * Draws the weave maze. The walls of each cell are inset from its borders, and the passages
+ * leave the cell as short corridors, so a tunnel shows as a gap in the walls of the corridor
+ * it passes beneath.
+ */
+impl Drawable for weave_grid::WeaveGrid {
+    fn draw(&self, img: &Image) -> RgbImage {
+        let thickness = img.wall_thickness() as f64;
+        let size = (img.cell_size() + img.wall_thickness()) as f64;
+        let inset = (size / 5.0).round();
+
+        let width = (size * self.width as f64 + thickness) as u32;
+        let height = (size * self.height as f64 + thickness) as u32;
+
+        let mut image: RgbImage = ImageBuffer::new(width, height);
+        // clears the background and sets it to a WHITE
+        draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+        let mut line = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            draw::thick_line(&mut image, (x1, y1), (x2, y2), thickness, BLACK);
+        };
+
+        for cell in self.cells.iter().flatten().flatten() {
+            let c = RefCell::borrow(cell);
+
+            // the borders of the cell, and the inset walls
+            let x1 = c.col as f64 * size + thickness / 2.0;
+            let y1 = c.row as f64 * size + thickness / 2.0;
+            let (x4, y4) = (x1 + size, y1 + size);
+            let (x2, y2, x3, y3) = (x1 + inset, y1 + inset, x4 - inset, y4 - inset);
+
+            let is_open = |side: &Option<_>| side.as_ref().is_some_and(|n| c.is_open_towards(n));
+
+            if is_open(&c.north) {
+                line(x2, y1, x2, y2);
+                line(x3, y1, x3, y2);
+            } else {
+                line(x2, y2, x3, y2);
+            }
+            if is_open(&c.south) {
+                line(x2, y3, x2, y4);
+                line(x3, y3, x3, y4);
+            } else {
+                line(x2, y3, x3, y3);
+            }
+            if is_open(&c.west) {
+                line(x1, y2, x2, y2);
+                line(x1, y3, x2, y3);
+            } else {
+                line(x2, y2, x2, y3);
+            }
+            if is_open(&c.east) {
+                line(x3, y2, x4, y2);
+                line(x3, y3, x4, y3);
+            } else {
+                line(x3, y2, x3, y3);
+            }
+
+            // the tunnel beneath the cell only shows between the cell and its neighbours
+            if let Some(under) = &c.under {
+                if RefCell::borrow(under).is_vertical_tunnel() {
+                    line(x2, y1, x2, y2);
+                    line(x3, y1, x3, y2);
+                    line(x2, y3, x2, y4);
+                    line(x3, y3, x3, y4);
+                } else {
+                    line(x1, y2, x2, y2);
+                    line(x1, y3, x2, y3);
+                    line(x3, y2, x4, y2);
+                    line(x3, y3, x4, y3);
+                }
+            }
+        }
+
+        image
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algos::recursive_backtracking::build_maze;
+
+
+    #[test]
+    fn draw_weave() {
+        let mut grid = weave_grid::WeaveGrid::new(12, 10);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_weave.png");
+
+        let saved = image::open("test_maze_weave.png").unwrap();
+        assert_eq!((266, 222), (saved.width(), saved.height()));
+    }
+}
@@ -0,0 +1,129 @@
+use image::{ImageBuffer, RgbImage};
+use imageproc::drawing::draw_filled_rect_mut;
+use imageproc::rect::Rect;
+
+use crate::maze::{cell, grid};
+use crate::presenter::draw;
+use crate::presenter::to_img::{Image, BLACK, WHITE};
+
+
+/*
+ * Draws the zeta maze, whose passages may also lead diagonally through the corners of the
+ * cells. The walls end short of each corner, and where a diagonal passage runs through
+ * the corner, the two cells it squeezes in between get their corners cut off instead, so
+ * the passage is as wide as the cut is long.
+ */
+pub(crate) fn draw(grid: &grid::Grid, img: &Image) -> RgbImage {
+    let thickness = img.wall_thickness() as f64;
+    let size = (img.cell_size() + img.wall_thickness()) as f64;
+    // the length of the wall left out at each corner
+    let cut = size / 3.0;
+
+    let width = (size * grid.width as f64 + thickness) as u32;
+    let height = (size * grid.height as f64 + thickness) as u32;
+
+    let mut image: RgbImage = ImageBuffer::new(width, height);
+    // clears the background and sets it to a WHITE
+    draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(width, height), WHITE);
+
+    // the centre of the walls meeting at the specified corner of the grid
+    let corner = |row: usize, col: usize| {
+        (col as f64 * size + thickness / 2.0, row as f64 * size + thickness / 2.0)
+    };
+    let get = |row: usize, col: usize| -> Option<&cell::CellStrongLink> {
+        grid.cells.get(row)?.get(col)?.as_ref()
+    };
+    let line = |image: &mut RgbImage, start: (f64, f64), end: (f64, f64)| {
+        draw::thick_line(image, start, end, thickness, BLACK);
+    };
+
+    // draws the middle parts of the walls along the top and left side of every cell
+    for row in 0..=grid.height as usize {
+        for col in 0..=grid.width as usize {
+            let (x, y) = corner(row, col);
+            let here = get(row, col);
+
+            if col < grid.width as usize && is_wall(row.checked_sub(1).and_then(|r| get(r, col)), here) {
+                line(&mut image, (x + cut, y), (x + size - cut, y));
+            }
+            if row < grid.height as usize && is_wall(col.checked_sub(1).and_then(|c| get(row, c)), here) {
+                line(&mut image, (x, y + cut), (x, y + size - cut));
+            }
+        }
+    }
+
+    // draws the walls around each corner of the grid
+    for row in 0..=grid.height as usize {
+        for col in 0..=grid.width as usize {
+            let (x, y) = corner(row, col);
+
+            let top_left = row.checked_sub(1).zip(col.checked_sub(1)).and_then(|(r, c)| get(r, c));
+            let top_right = row.checked_sub(1).and_then(|r| get(r, col));
+            let bottom_left = col.checked_sub(1).and_then(|c| get(row, c));
+            let bottom_right = get(row, col);
+
+            if is_passage(top_left, bottom_right) {
+                // cuts off the corners of the other two cells
+                line(&mut image, (x, y - cut), (x + cut, y));
+                line(&mut image, (x - cut, y), (x, y + cut));
+            } else if is_passage(top_right, bottom_left) {
+                line(&mut image, (x - cut, y), (x, y - cut));
+                line(&mut image, (x, y + cut), (x + cut, y));
+            } else {
+                // joins the walls meeting at the corner
+                for (a, b, end) in [(top_left, top_right, (x, y - cut)),
+                                    (bottom_left, bottom_right, (x, y + cut)),
+                                    (top_left, bottom_left, (x - cut, y)),
+                                    (top_right, bottom_right, (x + cut, y))] {
+                    if is_wall(a, b) {
+                        line(&mut image, (x, y), end);
+                    }
+                }
+            }
+        }
+    }
+
+    grid.draw_one_way_arrows(&mut image, img);
+    grid.draw_portals(&mut image, img);
+
+    image
+}
+
+
+// checks if there is a wall between both cells, which holds along the border of the grid
+fn is_wall(a: Option<&cell::CellStrongLink>, b: Option<&cell::CellStrongLink>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => !cell::is_open(a, b),
+        (Some(_), None) | (None, Some(_)) => true,
+        (None, None) => false,
+    }
+}
+
+
+// checks if there is a passage between both cells, leading either way
+fn is_passage(a: Option<&cell::CellStrongLink>, b: Option<&cell::CellStrongLink>) -> bool {
+    matches!((a, b), (Some(a), Some(b)) if cell::is_open(a, b))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::algos::recursive_backtracking::build_maze;
+    use crate::maze::grid;
+    use crate::presenter::to_img::Image;
+
+
+    #[test]
+    fn draw_zeta() {
+        let mut grid = grid::Grid::new_zeta(12, 10);
+        grid.init_grid();
+
+        build_maze(&grid, &mut rand::thread_rng());
+
+        let img = Image::new(20, 2);
+        img.save(&grid, "test_maze_zeta.png");
+
+        let saved = image::open("test_maze_zeta.png").unwrap();
+        assert_eq!((266, 222), (saved.width(), saved.height()));
+    }
+}
@@ -0,0 +1,107 @@
+use rand::RngCore;
+
+
+/*
+ * The sampling functions used by all algorithms instead of the ones provided by the 'rand'
+ * crate, as the latter may consume the random numbers differently in its future versions.
+ * They only rely on the raw output of the generator, hence the seeded mazes stay reproducible
+ * across the versions of the 'rand' crate. The stable generation keeps frozen copies of its own,
+ * see 'algos::v1'.
+ */
+
+
+/*
+ * Returns a uniformly distributed index lower than 'len', which shall be positive.
+ */
+pub fn gen_index<R: RngCore + ?Sized>(rng: &mut R, len: usize) -> usize {
+    assert!(len > 0, "cannot sample from an empty range");
+
+    // rejects the values from the incomplete last block, which would introduce a bias
+    let len = len as u64;
+    let limit = u64::MAX - u64::MAX % len;
+    loop {
+        let value = rng.next_u64();
+        if value < limit {
+            return (value % len) as usize;
+        }
+    }
+}
+
+
+/*
+ * Returns a uniformly distributed bool.
+ */
+pub fn gen_bool<R: RngCore + ?Sized>(rng: &mut R) -> bool {
+    rng.next_u64() >> 63 == 1
+}
+
+
+/*
+ * Returns a uniformly distributed float in the range '[0, 1)'.
+ */
+pub fn gen_unit<R: RngCore + ?Sized>(rng: &mut R) -> f64 {
+    // the 53 upper bits fill the whole mantissa
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+
+/*
+ * Returns a random element of the slice, or None if it is empty.
+ */
+pub fn choose<'a, T, R: RngCore + ?Sized>(rng: &mut R, slice: &'a [T]) -> Option<&'a T> {
+    if slice.is_empty() {
+        return None;
+    }
+    Some(&slice[gen_index(rng, slice.len())])
+}
+
+
+/*
+ * Shuffles the slice in place using the Fisher-Yates algorithm.
+ */
+pub fn shuffle<T, R: RngCore + ?Sized>(rng: &mut R, slice: &mut [T]) {
+    for i in (1..slice.len()).rev() {
+        slice.swap(i, gen_index(rng, i + 1));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::stable::{StableRng, Version};
+
+
+    #[test]
+    fn sample_gen_index_in_range() {
+        let mut rng = StableRng::new(Version::V1, 3);
+
+        assert!((0..1000).all(|_| gen_index(&mut rng, 7) < 7));
+    }
+
+
+    #[test]
+    fn sample_choose_empty() {
+        let empty: [u8; 0] = [];
+
+        assert!(choose(&mut StableRng::new(Version::V1, 3), &empty).is_none());
+    }
+
+
+    #[test]
+    fn sample_shuffle_keeps_elements() {
+        let mut values: Vec<u32> = (0..20).collect();
+        shuffle(&mut StableRng::new(Version::V1, 3), &mut values);
+        values.sort();
+
+        assert_eq!((0..20).collect::<Vec<u32>>(), values);
+    }
+
+
+    #[test]
+    fn sample_gen_unit_in_range() {
+        let mut rng = StableRng::new(Version::V1, 3);
+
+        assert!((0..1000).map(|_| gen_unit(&mut rng)).all(|x| (0.0..1.0).contains(&x)));
+    }
+}
@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use crate::maze::grid;
+use crate::maze::topology::MazeCell;
+
+
+/*
+ * The distances of all cells of the grid from a single root cell, measured as the number
+ * of steps along the passages of the maze. Unreachable cells have no distance.
+ */
+pub struct Distances {
+    cells: Vec<Vec<Option<u32>>>,
+}
+
+
+impl Distances {
+    /*
+     * Computes the distances from the cell at the specified row and column using
+     * the breadth-first search.
+     */
+    pub fn from(grid: &grid::Grid, root: (u16, u16)) -> Self {
+        let mut cells = vec![vec![None; grid.width as usize]; grid.height as usize];
+        cells[root.0 as usize][root.1 as usize] = Some(0);
+
+        let mut queue = VecDeque::from([root]);
+        while let Some((row, col)) = queue.pop_front() {
+            let distance = cells[row as usize][col as usize].unwrap();
+            let cell = grid.cells[row as usize][col as usize].as_ref().unwrap();
+
+            for link in RefCell::borrow(cell).links() {
+                let link = link.upgrade().unwrap();
+                let (r, c) = {
+                    let l = RefCell::borrow(&link);
+                    (l.row, l.col)
+                };
+
+                if cells[r as usize][c as usize].is_none() {
+                    cells[r as usize][c as usize] = Some(distance + 1);
+                    queue.push_back((r, c));
+                }
+            }
+        }
+
+        Self {
+            cells,
+        }
+    }
+
+
+    /*
+     * Returns the distance of the cell at the specified row and column from the root.
+     */
+    pub fn get(&self, row: u16, col: u16) -> Option<u32> {
+        self.cells[row as usize][col as usize]
+    }
+
+
+    /*
+     * Returns the cell furthest from the root, and its distance, which is the root itself
+     * when no other cell is reachable.
+     */
+    #[cfg(test)]
+    pub fn max(&self) -> ((u16, u16), u32) {
+        let mut furthest: Option<((u16, u16), u32)> = None;
+
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (col, distance) in cells.iter().enumerate() {
+                if let Some(distance) = *distance {
+                    if furthest.is_none_or(|(_, max)| distance > max) {
+                        furthest = Some(((row as u16, col as u16), distance));
+                    }
+                }
+            }
+        }
+
+        furthest.unwrap()
+    }
+}
+
+
+/*
+ * Returns the length of the shortest path between the two cells, or None if there is none.
+ */
+pub fn solution_length(grid: &grid::Grid, start: (u16, u16), goal: (u16, u16)) -> Option<u32> {
+    Distances::from(grid, start).get(goal.0, goal.1)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+
+    // links the cells of the grid into a single corridor going row by row, like a snake
+    fn snake(width: u16, height: u16) -> grid::Grid {
+        let mut grid = grid::Grid::new(width, height);
+        grid.init_grid();
+
+        let mut order = vec![];
+        for row in 0..height as usize {
+            let mut cols: Vec<usize> = (0..width as usize).collect();
+            if row % 2 == 1 {
+                cols.reverse();
+            }
+            order.extend(cols.into_iter().map(|col| (row, col)));
+        }
+
+        for pair in order.windows(2) {
+            let a = grid.cells[pair[0].0][pair[0].1].as_ref().unwrap();
+            let b = grid.cells[pair[1].0][pair[1].1].as_ref().unwrap();
+            RefCell::borrow_mut(a).link_to(Rc::downgrade(b));
+            RefCell::borrow_mut(b).link_to(Rc::downgrade(a));
+        }
+
+        grid
+    }
+
+
+    #[test]
+    fn distances_along_corridor() {
+        let grid = snake(4, 3);
+        let distances = Distances::from(&grid, (0, 0));
+
+        assert_eq!(Some(4), distances.get(1, 3));
+        assert_eq!(((2, 3), 11), distances.max());
+    }
+
+
+    #[test]
+    fn distances_unreachable() {
+        let mut grid = grid::Grid::new(3, 3);
+        grid.init_grid();
+
+        assert_eq!(None, solution_length(&grid, (0, 0), (2, 2)));
+    }
+}